@@ -0,0 +1,70 @@
+// Run with `cargo bench`. Plain std timing, no external harness.
+
+use matrix::fixed::Mat4;
+use matrix::matrix::matrix;
+use matrix::matrix::Matrix;
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+// Deterministic pseudo-random entries in [-1, 1)
+fn filled(n: usize, seed: u64) -> Matrix<f64> {
+    let mut m = matrix![0.; n];
+    let mut state = seed;
+
+    for i in 0..n {
+        for j in 0..n {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            m[(i, j)] = (state >> 11) as f64 / (1u64 << 52) as f64 - 1.;
+        }
+    }
+
+    m
+}
+
+fn bench<T>(name: &str, n: usize, mut f: impl FnMut() -> T) {
+    // Repeat until at least 200ms have been spent, report the best run
+    let mut best = Duration::MAX;
+    let mut total = Duration::ZERO;
+    let mut runs = 0;
+
+    while total < Duration::from_millis(200) || runs < 3 {
+        let start = Instant::now();
+        black_box(f());
+        let elapsed = start.elapsed();
+
+        best = best.min(elapsed);
+        total += elapsed;
+        runs += 1;
+    }
+
+    println!(
+        "{:<12} n = {:<4} best {:>12.3?}  mean {:>12.3?}  ({} runs)",
+        name,
+        n,
+        best,
+        total / runs,
+        runs
+    );
+}
+
+fn main() {
+    for n in [16, 64, 128] {
+        let a = filled(n, 1);
+        let b = filled(n, 2);
+
+        bench("mul_mat", n, || a.mul_mat(&b));
+        bench("transpose", n, || a.transpose());
+        bench("row_echelon", n, || a.row_echelon());
+    }
+
+    // Stack-allocated 4x4 against the heap-allocated Matrix
+    let a = filled(4, 1);
+    let b = filled(4, 2);
+    let (sa, sb) = (Mat4::try_from(&a).unwrap(), Mat4::try_from(&b).unwrap());
+
+    bench("mul_mat", 4, || black_box(&a).mul_mat(black_box(&b)));
+    bench("Mat4 mul", 4, || black_box(sa) * black_box(sb));
+}
@@ -0,0 +1,364 @@
+use super::complex::Complex;
+use super::matrix::Matrix;
+use super::vector::Vector;
+use super::FieldBound;
+use super::FloatBound;
+use super::VectorSpace;
+
+// Entry-wise approximate comparison for float-based scalars and the
+// containers built on them. PartialOrd on Vector/Matrix only compares norms.
+pub trait ApproxEq {
+    type Epsilon: Copy;
+
+    fn default_epsilon() -> Self::Epsilon;
+    fn default_max_relative() -> Self::Epsilon;
+    fn default_max_ulps() -> u32 {
+        4
+    }
+
+    // |a - b| <= epsilon
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool;
+
+    // |a - b| <= epsilon, or |a - b| <= max(|a|, |b|) * max_relative
+    fn relative_eq(
+        &self,
+        other: &Self,
+        epsilon: Self::Epsilon,
+        max_relative: Self::Epsilon,
+    ) -> bool;
+
+    // |a - b| <= epsilon, or a and b are at most max_ulps representable floats apart
+    fn ulps_eq(&self, other: &Self, epsilon: Self::Epsilon, max_ulps: u32) -> bool;
+
+    fn approx_eq(&self, other: &Self) -> bool {
+        self.relative_eq(other, Self::default_epsilon(), Self::default_max_relative())
+    }
+}
+
+macro_rules! impl_approx_eq_float {
+    ($($t:ident) +) => {
+        $(
+            impl ApproxEq for $t {
+                type Epsilon = $t;
+
+                fn default_epsilon() -> $t {
+                    $t::EPSILON
+                }
+
+                fn default_max_relative() -> $t {
+                    $t::EPSILON
+                }
+
+                fn abs_diff_eq(&self, other: &Self, epsilon: $t) -> bool {
+                    *self == *other || (self - other).abs() <= epsilon
+                }
+
+                fn relative_eq(&self, other: &Self, epsilon: $t, max_relative: $t) -> bool {
+                    if self.abs_diff_eq(other, epsilon) {
+                        return true;
+                    }
+                    if self.is_infinite() || other.is_infinite() {
+                        return false;
+                    }
+
+                    let largest = self.abs().max(other.abs());
+                    (self - other).abs() <= largest * max_relative
+                }
+
+                fn ulps_eq(&self, other: &Self, epsilon: $t, max_ulps: u32) -> bool {
+                    if self.abs_diff_eq(other, epsilon) {
+                        return true;
+                    }
+                    if self.is_nan() || other.is_nan()
+                        || self.is_sign_negative() != other.is_sign_negative() {
+                        return false;
+                    }
+
+                    self.to_bits().abs_diff(other.to_bits()) <= max_ulps.into()
+                }
+            }
+        )+
+    };
+}
+
+impl_approx_eq_float!(f32 f64);
+
+// Compared component-wise
+impl<T: FloatBound + ApproxEq> ApproxEq for Complex<T> {
+    type Epsilon = T::Epsilon;
+
+    fn default_epsilon() -> Self::Epsilon {
+        T::default_epsilon()
+    }
+
+    fn default_max_relative() -> Self::Epsilon {
+        T::default_max_relative()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        self.re.abs_diff_eq(&other.re, epsilon) && self.im.abs_diff_eq(&other.im, epsilon)
+    }
+
+    fn relative_eq(
+        &self,
+        other: &Self,
+        epsilon: Self::Epsilon,
+        max_relative: Self::Epsilon,
+    ) -> bool {
+        self.re.relative_eq(&other.re, epsilon, max_relative)
+            && self.im.relative_eq(&other.im, epsilon, max_relative)
+    }
+
+    fn ulps_eq(&self, other: &Self, epsilon: Self::Epsilon, max_ulps: u32) -> bool {
+        self.re.ulps_eq(&other.re, epsilon, max_ulps)
+            && self.im.ulps_eq(&other.im, epsilon, max_ulps)
+    }
+}
+
+// Containers of different sizes are never equal
+impl<K: FieldBound + ApproxEq> ApproxEq for Vector<K> {
+    type Epsilon = K::Epsilon;
+
+    fn default_epsilon() -> Self::Epsilon {
+        K::default_epsilon()
+    }
+
+    fn default_max_relative() -> Self::Epsilon {
+        K::default_max_relative()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        self.len() == other.len()
+            && self
+                .into_iter()
+                .zip(other)
+                .all(|(a, b)| a.abs_diff_eq(b, epsilon))
+    }
+
+    fn relative_eq(
+        &self,
+        other: &Self,
+        epsilon: Self::Epsilon,
+        max_relative: Self::Epsilon,
+    ) -> bool {
+        self.len() == other.len()
+            && self
+                .into_iter()
+                .zip(other)
+                .all(|(a, b)| a.relative_eq(b, epsilon, max_relative))
+    }
+
+    fn ulps_eq(&self, other: &Self, epsilon: Self::Epsilon, max_ulps: u32) -> bool {
+        self.len() == other.len()
+            && self
+                .into_iter()
+                .zip(other)
+                .all(|(a, b)| a.ulps_eq(b, epsilon, max_ulps))
+    }
+}
+
+impl<K: FieldBound + ApproxEq> ApproxEq for Matrix<K> {
+    type Epsilon = K::Epsilon;
+
+    fn default_epsilon() -> Self::Epsilon {
+        K::default_epsilon()
+    }
+
+    fn default_max_relative() -> Self::Epsilon {
+        K::default_max_relative()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        self.shape() == other.shape()
+            && self
+                .as_slice()
+                .iter()
+                .zip(other.as_slice())
+                .all(|(a, b)| a.abs_diff_eq(b, epsilon))
+    }
+
+    fn relative_eq(
+        &self,
+        other: &Self,
+        epsilon: Self::Epsilon,
+        max_relative: Self::Epsilon,
+    ) -> bool {
+        self.shape() == other.shape()
+            && self
+                .as_slice()
+                .iter()
+                .zip(other.as_slice())
+                .all(|(a, b)| a.relative_eq(b, epsilon, max_relative))
+    }
+
+    fn ulps_eq(&self, other: &Self, epsilon: Self::Epsilon, max_ulps: u32) -> bool {
+        self.shape() == other.shape()
+            && self
+                .as_slice()
+                .iter()
+                .zip(other.as_slice())
+                .all(|(a, b)| a.ulps_eq(b, epsilon, max_ulps))
+    }
+}
+
+// Without an epsilon, entries are compared with relative_eq and the
+// default tolerances; with one, with abs_diff_eq.
+#[macro_export]
+macro_rules! assert_vector_approx_eq {
+    ($left:expr, $right:expr) => {
+        $crate::assert_vector_approx_eq!(@check $left, $right, |a, b| {
+            $crate::approx::ApproxEq::approx_eq(a, b)
+        })
+    };
+
+    ($left:expr, $right:expr, $epsilon:expr) => {
+        $crate::assert_vector_approx_eq!(@check $left, $right, |a, b| {
+            $crate::approx::ApproxEq::abs_diff_eq(a, b, $epsilon)
+        })
+    };
+
+    (@check $left:expr, $right:expr, $eq:expr) => {
+        {
+            let (left, right) = (&$left, &$right);
+            if left.len() != right.len() {
+                panic!(
+                    "assertion failed: vectors have different lengths\n  left: {}\n right: {}",
+                    left.len(),
+                    right.len()
+                );
+            }
+            for i in 0..left.len() {
+                if !$eq(&left[i], &right[i]) {
+                    panic!(
+                        "assertion failed: vectors differ at index {}\n  left: {:?}\n right: {:?}",
+                        i, left[i], right[i]
+                    );
+                }
+            }
+        }
+    };
+}
+pub use assert_vector_approx_eq;
+
+#[macro_export]
+macro_rules! assert_matrix_approx_eq {
+    ($left:expr, $right:expr) => {
+        $crate::assert_matrix_approx_eq!(@check $left, $right, |a, b| {
+            $crate::approx::ApproxEq::approx_eq(a, b)
+        })
+    };
+
+    ($left:expr, $right:expr, $epsilon:expr) => {
+        $crate::assert_matrix_approx_eq!(@check $left, $right, |a, b| {
+            $crate::approx::ApproxEq::abs_diff_eq(a, b, $epsilon)
+        })
+    };
+
+    (@check $left:expr, $right:expr, $eq:expr) => {
+        {
+            let (left, right) = (&$left, &$right);
+            if left.n_rows() != right.n_rows() || left.n_cols() != right.n_cols() {
+                panic!(
+                    "assertion failed: matrices have different shapes\n  left: {}x{}\n right: {}x{}",
+                    left.n_rows(),
+                    left.n_cols(),
+                    right.n_rows(),
+                    right.n_cols()
+                );
+            }
+            for i in 0..left.n_rows() {
+                for j in 0..left.n_cols() {
+                    if !$eq(&left[(i, j)], &right[(i, j)]) {
+                        panic!(
+                            "assertion failed: matrices differ at index ({}, {})\n  left: {:?}\n right: {:?}",
+                            i, j, left[(i, j)], right[(i, j)]
+                        );
+                    }
+                }
+            }
+        }
+    };
+}
+pub use assert_matrix_approx_eq;
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::matrix::matrix;
+    use crate::vector::vector;
+
+    #[test]
+    fn scalar_test() {
+        let a = 0.1 + 0.2;
+
+        assert_ne!(a, 0.3);
+        assert!(a.approx_eq(&0.3));
+        assert!(a.abs_diff_eq(&0.3, 1e-15));
+        assert!(!1.0.abs_diff_eq(&1.1, 0.05));
+        assert!(1e9.relative_eq(&(1e9 + 1.), 0., 1e-8));
+        assert!(!1e9.relative_eq(&(1e9 + 1.), 0., 1e-10));
+        assert!(1f32.ulps_eq(&(1. + f32::EPSILON), 0., 1));
+        assert!(!1f64.ulps_eq(&(1. + 4. * f64::EPSILON), 0., 2));
+        assert!(!(-1e-300f64).ulps_eq(&1e-300, 0., 4));
+        assert!(!f64::NAN.approx_eq(&f64::NAN));
+        assert!(f64::INFINITY.approx_eq(&f64::INFINITY));
+    }
+
+    #[test]
+    fn complex_test() {
+        let a = Complex::new(0.1 + 0.2, 1.);
+
+        assert!(a.approx_eq(&Complex::new(0.3, 1.)));
+        assert!(!a.abs_diff_eq(&Complex::new(0.3, 1.1), 0.05));
+    }
+
+    #[test]
+    fn vector_test() {
+        let u = vector![0.1 + 0.2, 1.];
+
+        assert!(u.approx_eq(&vector![0.3, 1.]));
+        assert!(u.abs_diff_eq(&vector![0.3, 1.001], 1e-2));
+        assert!(!u.approx_eq(&vector![0.3, 1., 0.]));
+        // Same norm, different entries
+        assert!(!u.approx_eq(&vector![1., 0.3]));
+
+        assert_vector_approx_eq!(u, vector![0.3, 1.]);
+        assert_vector_approx_eq!(u, vector![0.3, 1.001], 1e-2);
+    }
+
+    #[test]
+    fn matrix_test() {
+        let m = matrix![[0.1 + 0.2, 1.], [2., 3.]];
+
+        assert!(m.approx_eq(&matrix![[0.3, 1.], [2., 3.]]));
+        assert!(!m.approx_eq(&matrix![[0.3, 2.], [1., 3.]]));
+        assert!(!m.approx_eq(&matrix![[0.3, 1.]]));
+        assert!(m.ulps_eq(&matrix![[0.3, 1.], [2., 3.]], 0., 1));
+
+        assert_matrix_approx_eq!(m, matrix![[0.3, 1.], [2., 3.]]);
+        assert_matrix_approx_eq!(m, matrix![[0.3, 1.], [2., 3.01]], 0.1);
+    }
+
+    #[test]
+    #[should_panic(expected = "vectors differ at index 1")]
+    fn assert_vector_approx_eq_test() {
+        assert_vector_approx_eq!(vector![1., 2., 3.], vector![1., 2.5, 3.]);
+    }
+
+    #[test]
+    #[should_panic(expected = "matrices differ at index (1, 0)")]
+    fn assert_matrix_approx_eq_test() {
+        assert_matrix_approx_eq!(
+            matrix![[1., 2.], [3., 4.]],
+            matrix![[1., 2.], [3.1, 4.]],
+            0.01
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "different shapes")]
+    fn assert_matrix_shape_test() {
+        assert_matrix_approx_eq!(matrix![[1., 2.]], matrix![[1.], [2.]]);
+    }
+}
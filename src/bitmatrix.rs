@@ -0,0 +1,351 @@
+use super::matrix::matrix;
+use super::matrix::Matrix;
+
+use std::fmt;
+
+const WORD: usize = u64::BITS as usize;
+
+// Dense matrix over GF(2), rows packed 64 entries per word.
+// Bits past the last column are always kept clear.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitMatrix {
+    rows: usize,
+    cols: usize,
+    stride: usize, // words per row
+    words: Vec<u64>,
+}
+
+impl BitMatrix {
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        let stride = cols.div_ceil(WORD);
+        BitMatrix {
+            rows,
+            cols,
+            stride,
+            words: vec![0; rows * stride],
+        }
+    }
+
+    pub fn identity(n: usize) -> Self {
+        let mut m = BitMatrix::zeros(n, n);
+        for i in 0..n {
+            m.set(i, i, true);
+        }
+        m
+    }
+
+    pub fn n_rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn n_cols(&self) -> usize {
+        self.cols
+    }
+
+    fn check_bounds(&self, i: usize, j: usize) {
+        if i >= self.rows || j >= self.cols {
+            panic!(
+                "index ({}, {}) out of bounds for a {}x{} bit matrix",
+                i, j, self.rows, self.cols
+            );
+        }
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> bool {
+        self.check_bounds(i, j);
+        self.words[i * self.stride + j / WORD] >> (j % WORD) & 1 == 1
+    }
+
+    pub fn set(&mut self, i: usize, j: usize, bit: bool) {
+        self.check_bounds(i, j);
+        let w = &mut self.words[i * self.stride + j / WORD];
+        if bit {
+            *w |= 1 << (j % WORD);
+        } else {
+            *w &= !(1 << (j % WORD));
+        }
+    }
+
+    pub fn flip(&mut self, i: usize, j: usize) {
+        self.check_bounds(i, j);
+        self.words[i * self.stride + j / WORD] ^= 1 << (j % WORD);
+    }
+
+    pub fn row(&self, i: usize) -> &[u64] {
+        &self.words[i * self.stride..(i + 1) * self.stride]
+    }
+
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        if a == b {
+            return;
+        }
+        for w in 0..self.stride {
+            self.words.swap(a * self.stride + w, b * self.stride + w);
+        }
+    }
+
+    // row[dst] ^= row[src], starting from word `from`
+    fn xor_row_into(&mut self, src: usize, dst: usize, from: usize) {
+        for w in from..self.stride {
+            self.words[dst * self.stride + w] ^= self.words[src * self.stride + w];
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn transpose(&self) -> BitMatrix {
+        let mut t = BitMatrix::zeros(self.cols, self.rows);
+        for i in 0..self.rows {
+            for j in 0..self.cols {
+                if self.get(i, j) {
+                    t.set(j, i, true);
+                }
+            }
+        }
+        t
+    }
+
+    pub fn mul_mat(&self, m: &BitMatrix) -> BitMatrix {
+        if self.cols != m.rows {
+            panic!("matrices are incompatible for matrix multiplication");
+        }
+
+        // Row i of the product is the XOR of the rows of m selected by row i
+        let mut prod = BitMatrix::zeros(self.rows, m.cols);
+        for i in 0..self.rows {
+            for k in 0..self.cols {
+                if self.get(i, k) {
+                    for w in 0..m.stride {
+                        prod.words[i * prod.stride + w] ^= m.words[k * m.stride + w];
+                    }
+                }
+            }
+        }
+        prod
+    }
+
+    // In-place Gauss-Jordan elimination, returns the pivot columns
+    fn reduce(&mut self) -> Vec<usize> {
+        let mut pivots = vec![];
+        let mut r = 0;
+
+        for j in 0..self.cols {
+            if r == self.rows {
+                break;
+            }
+
+            let Some(p) = (r..self.rows).find(|&i| self.get(i, j)) else {
+                continue;
+            };
+            self.swap_rows(p, r);
+
+            for i in 0..self.rows {
+                if i != r && self.get(i, j) {
+                    self.xor_row_into(r, i, j / WORD);
+                }
+            }
+
+            pivots.push(j);
+            r += 1;
+        }
+
+        pivots
+    }
+
+    pub fn row_echelon(&self) -> BitMatrix {
+        // Return reduced row-echelon form
+        let mut m = self.clone();
+        m.reduce();
+        m
+    }
+
+    pub fn rank(&self) -> usize {
+        self.clone().reduce().len()
+    }
+
+    // Basis of { x : self x = 0 }, one vector per row
+    pub fn null_space(&self) -> BitMatrix {
+        let mut m = self.clone();
+        let pivots = m.reduce();
+
+        let mut is_pivot = vec![false; self.cols];
+        for &p in &pivots {
+            is_pivot[p] = true;
+        }
+        let free: Vec<usize> = (0..self.cols).filter(|&j| !is_pivot[j]).collect();
+        let mut basis = BitMatrix::zeros(free.len(), self.cols);
+
+        for (k, &f) in free.iter().enumerate() {
+            basis.set(k, f, true);
+            for (i, &p) in pivots.iter().enumerate() {
+                if m.get(i, f) {
+                    basis.set(k, p, true);
+                }
+            }
+        }
+
+        basis
+    }
+}
+
+// Entries are reduced mod 2, so any odd value becomes a one
+impl From<&Matrix<i8>> for BitMatrix {
+    fn from(m: &Matrix<i8>) -> Self {
+        let mut bits = BitMatrix::zeros(m.n_rows(), m.n_cols());
+        for i in 0..m.n_rows() {
+            for j in 0..m.n_cols() {
+                if m[(i, j)] % 2 != 0 {
+                    bits.set(i, j, true);
+                }
+            }
+        }
+        bits
+    }
+}
+
+impl From<&BitMatrix> for Matrix<i8> {
+    fn from(bits: &BitMatrix) -> Self {
+        let mut m = matrix![0; bits.rows, bits.cols];
+        for i in 0..bits.rows {
+            for j in 0..bits.cols {
+                if bits.get(i, j) {
+                    m[(i, j)] = 1;
+                }
+            }
+        }
+        m
+    }
+}
+
+impl fmt::Display for BitMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for i in 0..self.rows {
+            for j in 0..self.cols {
+                write!(f, "{}", if self.get(i, j) { '1' } else { '0' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    // Parity-check matrix of the [7, 4] Hamming code
+    fn hamming_h() -> BitMatrix {
+        BitMatrix::from(&matrix![
+            [1, 0, 1, 0, 1, 0, 1],
+            [0, 1, 1, 0, 0, 1, 1],
+            [0, 0, 0, 1, 1, 1, 1]
+        ])
+    }
+
+    #[test]
+    fn get_set_test() {
+        let mut m = BitMatrix::zeros(2, 130);
+
+        m.set(1, 129, true);
+        m.flip(0, 64);
+        assert!(m.get(1, 129) && m.get(0, 64));
+        assert!(!m.get(1, 128));
+        assert_eq!(m.count_ones(), 2);
+
+        m.set(1, 129, false);
+        m.flip(0, 64);
+        assert!(m.is_zero());
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_bounds_test() {
+        BitMatrix::zeros(2, 3).get(0, 3);
+    }
+
+    #[test]
+    fn conversion_test() {
+        let m: Matrix<i8> = matrix![[1, 0, 3], [-1, 2, 4]];
+        let bits = BitMatrix::from(&m);
+
+        assert_eq!(bits.to_string(), "101\n100\n");
+        assert_eq!(Matrix::from(&bits), matrix![[1, 0, 1], [1, 0, 0]]);
+    }
+
+    #[test]
+    fn mul_mat_test() {
+        let a = BitMatrix::from(&matrix![[1, 1, 0], [0, 1, 1]]);
+        let b = BitMatrix::from(&matrix![[1, 0], [1, 1], [0, 1]]);
+
+        assert_eq!(Matrix::from(&a.mul_mat(&b)), matrix![[0, 1], [1, 0]]);
+        assert_eq!(a.mul_mat(&BitMatrix::identity(3)), a);
+        assert_eq!(
+            a.transpose(),
+            BitMatrix::from(&matrix![[1, 0], [1, 1], [0, 1]])
+        );
+    }
+
+    #[test]
+    fn row_echelon_test() {
+        let h = hamming_h();
+
+        assert_eq!(h.rank(), 3);
+        assert_eq!(
+            Matrix::from(&h.row_echelon()),
+            matrix![
+                [1, 0, 1, 0, 1, 0, 1],
+                [0, 1, 1, 0, 0, 1, 1],
+                [0, 0, 0, 1, 1, 1, 1]
+            ]
+        );
+
+        let m = BitMatrix::from(&matrix![[1, 1, 0], [0, 1, 1], [1, 0, 1]]);
+
+        assert_eq!(m.rank(), 2);
+        assert_eq!(
+            Matrix::from(&m.row_echelon()),
+            matrix![[1, 0, 1], [0, 1, 1], [0, 0, 0]]
+        );
+    }
+
+    #[test]
+    fn null_space_test() {
+        let h = hamming_h();
+        let g = h.null_space();
+
+        // The generator spans the 4-dimensional code
+        assert_eq!(g.n_rows(), 4);
+        assert_eq!(g.rank(), 4);
+        assert!(h.mul_mat(&g.transpose()).is_zero());
+    }
+
+    #[test]
+    fn wide_matrix_test() {
+        // Rows spread across several words, with a dependent last row
+        let (rows, cols) = (5, 200);
+        let mut m = BitMatrix::zeros(rows, cols);
+        for i in 0..rows - 1 {
+            for j in (i..cols).step_by(i + 2) {
+                m.set(i, j, true);
+            }
+        }
+        for j in 0..cols {
+            if m.get(0, j) != m.get(2, j) {
+                m.set(rows - 1, j, true);
+            }
+        }
+
+        assert_eq!(m.rank(), 4);
+
+        let n = m.null_space();
+        assert_eq!(n.n_rows(), cols - 4);
+        assert!(m.mul_mat(&n.transpose()).is_zero());
+    }
+}
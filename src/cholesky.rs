@@ -0,0 +1,467 @@
+use super::FloatBound;
+use super::MatrixError;
+
+use super::matrix::matrix;
+use super::matrix::Matrix;
+use super::vector::vector;
+use super::vector::Vector;
+
+// A = L L^T with L lower triangular
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cholesky<K: FloatBound> {
+    l: Matrix<K>,
+}
+
+// Bunch-Kaufman P A P^T = L D L^T with L unit lower triangular and D block
+// diagonal with 1x1 and 2x2 blocks
+#[derive(Debug, Clone, PartialEq)]
+pub struct LDLT<K: FloatBound> {
+    l: Matrix<K>,
+    diag: Vector<K>,
+    sub: Vector<K>,   // sub[k] = D[k + 1][k], non-zero only inside 2x2 blocks
+    perm: Vec<usize>, // row i of PAP^T is row perm[i] of A
+}
+
+// Bunch-Kaufman pivot growth bound (1 + sqrt(17)) / 8
+const BUNCH_KAUFMAN_ALPHA: f64 = 0.6403882032022076;
+
+impl<K: FloatBound> Matrix<K> {
+    pub(crate) fn check_symmetric(&self, operation: &'static str) -> Result<(), MatrixError> {
+        self.check_square(operation)?;
+
+        let n = self.n_rows();
+        let scale = K::EPSILON * K::from_f64(n as f64);
+
+        for i in 0..n {
+            for j in (i + 1)..n {
+                let (a, b) = (self[(i, j)], self[(j, i)]);
+                let max = if a.abs() > b.abs() { a.abs() } else { b.abs() };
+                if (a - b).abs() > scale * max {
+                    return Err(MatrixError::NotSymmetric);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn cholesky(&self) -> Result<Cholesky<K>, MatrixError> {
+        self.check_symmetric("cholesky")?;
+
+        let n = self.n_rows();
+        let mut l = matrix![K::ZERO; n];
+
+        for j in 0..n {
+            let mut s = self[(j, j)];
+            for k in 0..j {
+                s -= &(l[(j, k)] * l[(j, k)]);
+            }
+
+            if s <= K::ZERO {
+                return Err(MatrixError::NotPositiveDefinite { order: j + 1 });
+            }
+            l[(j, j)] = s.sqrt();
+
+            for i in (j + 1)..n {
+                let mut s = self[(i, j)];
+                for k in 0..j {
+                    s -= &(l[(i, k)] * l[(j, k)]);
+                }
+                l[(i, j)] = s / l[(j, j)];
+            }
+        }
+
+        Ok(Cholesky { l })
+    }
+
+    pub fn ldlt(&self) -> Result<LDLT<K>, MatrixError> {
+        self.check_symmetric("ldlt")?;
+
+        let n = self.n_rows();
+        let tol = self.default_tolerance();
+        let alpha = K::from_f64(BUNCH_KAUFMAN_ALPHA);
+        let mut a = self.clone();
+        let mut l = self.identity();
+        let mut diag = vector![K::ZERO; n];
+        let mut sub = vector![K::ZERO; n];
+        let mut perm: Vec<usize> = (0..n).collect();
+
+        let mut k = 0;
+        while k < n {
+            // Largest entry below the diagonal in column k
+            let (mut r, mut lambda) = (k, K::ZERO);
+            for i in (k + 1)..n {
+                if a[(i, k)].abs() > lambda {
+                    (r, lambda) = (i, a[(i, k)].abs());
+                }
+            }
+
+            let akk = a[(k, k)].abs();
+            if akk <= tol && lambda <= tol {
+                // Zero column: a singular 1x1 block, nothing to eliminate
+                k += 1;
+                continue;
+            }
+
+            let mut size = 1;
+            if akk < alpha * lambda {
+                let mut sigma = K::ZERO;
+                for j in k..n {
+                    if j != r && a[(j, r)].abs() > sigma {
+                        sigma = a[(j, r)].abs();
+                    }
+                }
+
+                if akk * sigma < alpha * lambda * lambda {
+                    if a[(r, r)].abs() >= alpha * sigma {
+                        a.swap_rows(k, r);
+                        a.swap_cols(k, r);
+                        swap_eliminated(&mut l, k, r, k);
+                        perm.swap(k, r);
+                    } else {
+                        a.swap_rows(k + 1, r);
+                        a.swap_cols(k + 1, r);
+                        swap_eliminated(&mut l, k + 1, r, k);
+                        perm.swap(k + 1, r);
+                        size = 2;
+                    }
+                }
+            }
+
+            if size == 1 {
+                let d = a[(k, k)];
+                let c: Vec<K> = ((k + 1)..n).map(|i| a[(i, k)]).collect();
+                for (i, &ci) in ((k + 1)..n).zip(&c) {
+                    l[(i, k)] = ci / d;
+                    for (j, &cj) in ((k + 1)..n).zip(&c) {
+                        a[(i, j)] -= &(ci * cj / d);
+                    }
+                }
+                diag[k] = d;
+            } else {
+                // Columns k + 2.. of L are the block columns times E^{-1}
+                let (e11, e21, e22) = (a[(k, k)], a[(k + 1, k)], a[(k + 1, k + 1)]);
+                let det = e11 * e22 - e21 * e21;
+                let c0: Vec<K> = ((k + 2)..n).map(|i| a[(i, k)]).collect();
+                let c1: Vec<K> = ((k + 2)..n).map(|i| a[(i, k + 1)]).collect();
+                for (t, i) in ((k + 2)..n).enumerate() {
+                    l[(i, k)] = (c0[t] * e22 - c1[t] * e21) / det;
+                    l[(i, k + 1)] = (c1[t] * e11 - c0[t] * e21) / det;
+                }
+                for i in (k + 2)..n {
+                    for (t, j) in ((k + 2)..n).enumerate() {
+                        a[(i, j)] -= &(l[(i, k)] * c0[t] + l[(i, k + 1)] * c1[t]);
+                    }
+                }
+                diag[k] = e11;
+                diag[k + 1] = e22;
+                sub[k] = e21;
+            }
+            k += size;
+        }
+
+        Ok(LDLT { l, diag, sub, perm })
+    }
+}
+
+// Swaps rows i and j of the columns of L computed so far
+fn swap_eliminated<K: FloatBound>(l: &mut Matrix<K>, i: usize, j: usize, computed: usize) {
+    for c in 0..computed {
+        let t = l[(i, c)];
+        l[(i, c)] = l[(j, c)];
+        l[(j, c)] = t;
+    }
+}
+
+fn check_rhs<K: FloatBound>(n: usize, b: &Vector<K>) -> Result<(), MatrixError> {
+    if b.len() != n {
+        return Err(MatrixError::size("solve", n, b.len()));
+    }
+    Ok(())
+}
+
+// Solves L y = b for unit (or general) lower triangular L
+fn forward_substitution<K: FloatBound>(l: &Matrix<K>, b: &Vector<K>, unit: bool) -> Vector<K> {
+    let n = b.len();
+    let mut y = b.clone();
+
+    for i in 0..n {
+        for k in 0..i {
+            y[i] = y[i] - l[(i, k)] * y[k];
+        }
+        if !unit {
+            y[i] /= &l[(i, i)];
+        }
+    }
+
+    y
+}
+
+// Solves L^T x = y
+fn backward_substitution<K: FloatBound>(l: &Matrix<K>, y: &Vector<K>, unit: bool) -> Vector<K> {
+    let n = y.len();
+    let mut x = y.clone();
+
+    for i in (0..n).rev() {
+        for k in (i + 1)..n {
+            x[i] = x[i] - l[(k, i)] * x[k];
+        }
+        if !unit {
+            x[i] /= &l[(i, i)];
+        }
+    }
+
+    x
+}
+
+impl<K: FloatBound> Cholesky<K> {
+    pub fn l(&self) -> Matrix<K> {
+        self.l.clone()
+    }
+
+    pub fn determinant(&self) -> K {
+        let mut det = K::ONE;
+        for i in 0..self.l.n_rows() {
+            det *= &(self.l[(i, i)] * self.l[(i, i)]);
+        }
+        det
+    }
+
+    pub fn solve(&self, b: &Vector<K>) -> Result<Vector<K>, MatrixError> {
+        check_rhs(self.l.n_rows(), b)?;
+
+        let y = forward_substitution(&self.l, b, false);
+        Ok(backward_substitution(&self.l, &y, false))
+    }
+}
+
+impl<K: FloatBound> LDLT<K> {
+    fn n(&self) -> usize {
+        self.perm.len()
+    }
+
+    // Start of a 2x2 block
+    fn is_block(&self, k: usize) -> bool {
+        !self.sub[k].is_zero()
+    }
+
+    pub fn l(&self) -> Matrix<K> {
+        self.l.clone()
+    }
+
+    pub fn d(&self) -> Matrix<K> {
+        let n = self.n();
+        let mut d = matrix![K::ZERO; n];
+
+        for k in 0..n {
+            d[(k, k)] = self.diag[k];
+            if self.is_block(k) {
+                d[(k + 1, k)] = self.sub[k];
+                d[(k, k + 1)] = self.sub[k];
+            }
+        }
+
+        d
+    }
+
+    pub fn p(&self) -> Matrix<K> {
+        let n = self.n();
+        let mut p = matrix![K::ZERO; n];
+
+        for (i, &r) in self.perm.iter().enumerate() {
+            p[(i, r)] = K::ONE;
+        }
+
+        p
+    }
+
+    pub fn permutation(&self) -> &[usize] {
+        &self.perm
+    }
+
+    pub fn determinant(&self) -> K {
+        let mut det = K::ONE;
+        let mut k = 0;
+        while k < self.n() {
+            if self.is_block(k) {
+                det *= &(self.diag[k] * self.diag[k + 1] - self.sub[k] * self.sub[k]);
+                k += 2;
+            } else {
+                det *= &self.diag[k];
+                k += 1;
+            }
+        }
+        det
+    }
+
+    pub fn solve(&self, b: &Vector<K>) -> Result<Vector<K>, MatrixError> {
+        let n = self.n();
+        check_rhs(n, b)?;
+
+        let mut y = vector![K::ZERO; n];
+        for i in 0..n {
+            y[i] = b[self.perm[i]];
+        }
+        let mut z = forward_substitution(&self.l, &y, true);
+
+        let mut k = 0;
+        while k < n {
+            if self.is_block(k) {
+                let (e11, e21, e22) = (self.diag[k], self.sub[k], self.diag[k + 1]);
+                let det = e11 * e22 - e21 * e21;
+                let (z0, z1) = (z[k], z[k + 1]);
+                z[k] = (e22 * z0 - e21 * z1) / det;
+                z[k + 1] = (e11 * z1 - e21 * z0) / det;
+                k += 2;
+            } else {
+                if self.diag[k].is_zero() {
+                    return Err(MatrixError::Singular);
+                }
+                z[k] /= &self.diag[k];
+                k += 1;
+            }
+        }
+
+        let v = backward_substitution(&self.l, &z, true);
+        let mut x = vector![K::ZERO; n];
+        for i in 0..n {
+            x[self.perm[i]] = v[i];
+        }
+        Ok(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::approx::{assert_matrix_approx_eq, assert_vector_approx_eq};
+
+    #[test]
+    fn cholesky_test() {
+        let a = matrix![[4., 12., -16.], [12., 37., -43.], [-16., -43., 98.]];
+        let chol = a.cholesky().unwrap();
+
+        assert_eq!(chol.l(), matrix![[2., 0., 0.], [6., 1., 0.], [-8., 5., 3.]]);
+        assert_eq!(chol.l().mul_mat(&chol.l().transpose()), a);
+        assert_eq!(chol.determinant(), 36.);
+    }
+
+    #[test]
+    fn cholesky_error_test() {
+        let a = matrix![[1., 2.], [2., 1.]];
+        let b = matrix![[1., 2.], [3., 4.]];
+
+        assert_eq!(
+            a.cholesky(),
+            Err(MatrixError::NotPositiveDefinite { order: 2 })
+        );
+        assert_eq!(b.cholesky(), Err(MatrixError::NotSymmetric));
+        assert_eq!(
+            a.cholesky().unwrap_err().to_string(),
+            "matrix is not positive definite: leading minor of order 2 is not positive"
+        );
+    }
+
+    #[test]
+    fn cholesky_solve_test() {
+        let a = matrix![[4., 12., -16.], [12., 37., -43.], [-16., -43., 98.]];
+        let chol = a.cholesky().unwrap();
+
+        assert_eq!(
+            chol.solve(&vector![0., 6., 39.]).unwrap(),
+            vector![1., 1., 1.]
+        );
+        assert!(matches!(
+            chol.solve(&vector![1., 2.]),
+            Err(MatrixError::Incompatible { .. })
+        ));
+    }
+
+    // P^T L D L^T P
+    fn reconstruct(f: &LDLT<f64>) -> Matrix<f64> {
+        let p = f.p();
+        p.transpose()
+            .mul_mat(&f.l())
+            .mul_mat(&f.d())
+            .mul_mat(&f.l().transpose())
+            .mul_mat(&p)
+    }
+
+    #[test]
+    fn ldlt_test() {
+        let a = matrix![[4., 12., -16.], [12., 37., -43.], [-16., -43., 98.]];
+        let ldlt = a.ldlt().unwrap();
+
+        assert_matrix_approx_eq!(reconstruct(&ldlt), a, 1e-12);
+        assert!((ldlt.determinant() - 36.).abs() < 1e-9);
+        assert_vector_approx_eq!(
+            ldlt.solve(&vector![0., 6., 39.]).unwrap(),
+            vector![1., 1., 1.],
+            1e-12
+        );
+
+        // Indefinite input, neither diagonal entry is a safe pivot
+        let b = matrix![[1., 2.], [2., 1.]];
+        let ldlt = b.ldlt().unwrap();
+
+        assert_eq!(ldlt.d(), b);
+        assert_eq!(ldlt.determinant(), -3.);
+        assert_eq!(ldlt.solve(&vector![3., 3.]).unwrap(), vector![1., 1.]);
+    }
+
+    #[test]
+    fn ldlt_pivoting_test() {
+        // Zero diagonal, solvable only through symmetric interchanges
+        let a = matrix![[0., 1.], [1., 0.]];
+        let ldlt = a.ldlt().unwrap();
+
+        assert_eq!(ldlt.determinant(), -1.);
+        assert_eq!(ldlt.solve(&vector![2., 3.]).unwrap(), vector![3., 2.]);
+
+        let a = matrix![
+            [0., 1., 2., 3.],
+            [1., 0., 4., 5.],
+            [2., 4., 1e-3, 6.],
+            [3., 5., 6., 0.]
+        ];
+        let ldlt = a.ldlt().unwrap();
+        let x = vector![1., -2., 3., -4.];
+
+        assert_matrix_approx_eq!(reconstruct(&ldlt), a, 1e-12);
+        assert_vector_approx_eq!(ldlt.solve(&a.mul_vec(&x)).unwrap(), x, 1e-12);
+        assert!((ldlt.determinant() - a.determinant()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn ldlt_semidefinite_test() {
+        let a = matrix![[1., 1., 0.], [1., 1., 0.], [0., 0., 2.]];
+        let ldlt = a.ldlt().unwrap();
+
+        assert_eq!(ldlt.d(), matrix![[1., 0., 0.], [0., 0., 0.], [0., 0., 2.]]);
+        assert!(matches!(
+            ldlt.solve(&vector![1., 1., 1.]),
+            Err(MatrixError::Singular)
+        ));
+    }
+
+    #[test]
+    fn symmetric_shape_test() {
+        let a = matrix![[1., 2., 3.], [2., 4., 5.]];
+
+        assert!(matches!(
+            a.cholesky(),
+            Err(MatrixError::NotSquare {
+                operation: "cholesky",
+                ..
+            })
+        ));
+        assert!(matches!(
+            a.ldlt(),
+            Err(MatrixError::NotSquare {
+                operation: "ldlt",
+                ..
+            })
+        ));
+    }
+}
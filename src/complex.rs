@@ -0,0 +1,295 @@
+use super::FieldBound;
+use super::FloatBound;
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Complex<T: FloatBound> {
+    pub re: T,
+    pub im: T,
+}
+
+impl<T: FloatBound> Complex<T> {
+    pub fn new(re: T, im: T) -> Self {
+        Complex { re, im }
+    }
+
+    pub fn i() -> Self {
+        Complex::new(T::ZERO, T::ONE)
+    }
+
+    pub fn norm_sqr(&self) -> T {
+        self.re * self.re + self.im * self.im
+    }
+
+    // |z|, scaled to avoid overflow in the squares
+    pub fn modulus(&self) -> T {
+        let (a, b) = (self.re.abs(), self.im.abs());
+        let (max, min) = if a > b { (a, b) } else { (b, a) };
+
+        if max.is_zero() {
+            return T::ZERO;
+        }
+
+        let r = min / max;
+        max * (T::ONE + r * r).sqrt()
+    }
+}
+
+impl<T: FloatBound> From<T> for Complex<T> {
+    fn from(re: T) -> Self {
+        Complex::new(re, T::ZERO)
+    }
+}
+
+impl<T: FloatBound> fmt::Display for Complex<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.im < T::ZERO { '-' } else { '+' };
+        let im = self.im.abs();
+
+        match f.precision() {
+            Some(p) => write!(f, "{:.*}{}{:.*}i", p, self.re, sign, p, im),
+            None => write!(f, "{}{}{}i", self.re, sign, im),
+        }
+    }
+}
+
+// Complex numbers are not ordered: only equal values and real ones compare,
+// which covers the moduli returned by abs() that pivoting relies on
+impl<T: FloatBound> PartialOrd for Complex<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self == other {
+            return Some(Ordering::Equal);
+        }
+        if self.im.is_zero() && other.im.is_zero() {
+            return self.re.partial_cmp(&other.re);
+        }
+        None
+    }
+}
+
+impl<T: FloatBound> ops::Neg for Complex<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Complex::new(-self.re, -self.im)
+    }
+}
+
+impl<T: FloatBound> ops::Add for Complex<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Complex::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+impl<T: FloatBound> ops::Sub for Complex<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Complex::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+impl<T: FloatBound> ops::Mul for Complex<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Complex::new(
+            self.re * rhs.re - self.im * rhs.im,
+            self.re * rhs.im + self.im * rhs.re,
+        )
+    }
+}
+
+impl<T: FloatBound> ops::Div for Complex<T> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        // Smith's algorithm
+        if rhs.re.abs() >= rhs.im.abs() {
+            let r = rhs.im / rhs.re;
+            let d = rhs.re + rhs.im * r;
+            Complex::new((self.re + self.im * r) / d, (self.im - self.re * r) / d)
+        } else {
+            let r = rhs.re / rhs.im;
+            let d = rhs.re * r + rhs.im;
+            Complex::new((self.re * r + self.im) / d, (self.im * r - self.re) / d)
+        }
+    }
+}
+
+macro_rules! impl_ops_assign {
+    ($trait:ty, $fun:ident, $op:tt) => {
+        impl<T: FloatBound> $trait for Complex<T> {
+            fn $fun(&mut self, rhs: &Self) {
+                *self = *self $op *rhs;
+            }
+        }
+    };
+}
+
+impl_ops_assign!(ops::AddAssign<&Self>, add_assign, +);
+impl_ops_assign!(ops::SubAssign<&Self>, sub_assign, -);
+impl_ops_assign!(ops::MulAssign<&Self>, mul_assign, *);
+impl_ops_assign!(ops::DivAssign<&Self>, div_assign, /);
+
+impl<T: FloatBound> FieldBound for Complex<T> {
+    const ZERO: Self = Complex {
+        re: T::ZERO,
+        im: T::ZERO,
+    };
+    const ONE: Self = Complex {
+        re: T::ONE,
+        im: T::ZERO,
+    };
+
+    // Modulus, as a real-valued complex number
+    fn abs(&self) -> Self {
+        Complex::from(self.modulus())
+    }
+
+    // Principal square root (non-negative real part)
+    fn sqrt(&self) -> Self {
+        if self.is_zero() {
+            return Self::ZERO;
+        }
+
+        let two = T::ONE + T::ONE;
+        let r = self.modulus();
+        let re = ((r + self.re) / two).sqrt();
+        let im = ((r - self.re) / two).sqrt();
+
+        if self.im < T::ZERO {
+            Complex::new(re, -im)
+        } else {
+            Complex::new(re, im)
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.re.is_zero() && self.im.is_zero()
+    }
+
+    fn epsilon() -> Self {
+        Complex::from(T::EPSILON)
+    }
+
+    fn conjugate(&self) -> Self {
+        Complex::new(self.re, -self.im)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::approx::{assert_matrix_approx_eq, ApproxEq};
+    use crate::matrix::matrix;
+    use crate::matrix::Matrix;
+    use crate::vector::vector;
+    use crate::VectorSpace;
+
+    fn c(re: f64, im: f64) -> Complex<f64> {
+        Complex::new(re, im)
+    }
+
+    #[test]
+    fn arithmetic_test() {
+        let (a, b) = (c(1., 2.), c(3., -1.));
+
+        assert_eq!(a + b, c(4., 1.));
+        assert_eq!(a - b, c(-2., 3.));
+        assert_eq!(a * b, c(5., 5.));
+        assert_eq!(-a, c(-1., -2.));
+        assert!((a / b).abs_diff_eq(&c(0.1, 0.7), 1e-12));
+        assert!(((a / b) * b).abs_diff_eq(&a, 1e-12));
+        assert_eq!(Complex::<f64>::i() * Complex::i(), c(-1., 0.));
+
+        let mut d = a;
+        d *= &b;
+        d -= &b;
+        assert_eq!(d, c(2., 6.));
+    }
+
+    #[test]
+    fn ordering_test() {
+        let one = c(1., 0.);
+
+        // Same modulus, but not equal, so not ordered either
+        assert_eq!(one.partial_cmp(&Complex::i()), None);
+        assert_eq!(c(1., 2.).partial_cmp(&c(1., 2.)), Some(Ordering::Equal));
+        assert!(c(2., 0.) > one);
+        assert!(c(3., 4.).abs() > c(-4., 0.).abs());
+    }
+
+    #[test]
+    fn abs_sqrt_test() {
+        assert_eq!(c(3., -4.).abs(), c(5., 0.));
+        assert_eq!(c(-4., 0.).sqrt(), c(0., 2.));
+        assert_eq!(c(3., 4.).sqrt(), c(2., 1.));
+        assert_eq!(c(3., -4.).sqrt(), c(2., -1.));
+        assert_eq!(Complex::<f64>::ZERO.sqrt(), Complex::ZERO);
+        assert!(c(0., 0.).is_zero());
+        assert_eq!(c(1., 2.).conjugate(), c(1., -2.));
+    }
+
+    #[test]
+    fn display_test() {
+        assert_eq!(c(1.5, -2.).to_string(), "1.5-2i");
+        assert_eq!(format!("{:.2}", c(1., 0.25)), "1.00+0.25i");
+    }
+
+    #[test]
+    fn vector_test() {
+        let u = vector![c(1., 1.), c(0., 2.)];
+        let v = vector![c(2., 0.), c(1., -1.)];
+
+        // conj(u) . v = (1 - i) 2 + (-2i)(1 - i)
+        assert_eq!(u.dot(&v), c(0., -4.));
+        assert_eq!(v.dot(&u), u.dot(&v).conjugate());
+        assert_eq!(u.dot(&u), c(6., 0.));
+        assert!(u.norm().abs_diff_eq(&c(6f64.sqrt(), 0.), 1e-12));
+        assert!(u.norm_1().abs_diff_eq(&c(2f64.sqrt() + 2., 0.), 1e-12));
+        assert_eq!(u.norm_inf(), c(2., 0.));
+    }
+
+    #[test]
+    fn matrix_test() {
+        let m: Matrix<Complex<f64>> = matrix![[c(1., 1.), c(2., 0.)], [c(0., -1.), c(1., 0.)]];
+        let n = matrix![[c(0., 1.), c(1., 0.)], [c(1., 0.), c(0., 0.)]];
+
+        assert_eq!(
+            m.mul_mat(&n),
+            matrix![[c(1., 1.), c(1., 1.)], [c(2., 0.), c(0., -1.)]]
+        );
+        assert_eq!(
+            m.mul_vec(&vector![c(1., 0.), c(0., 1.)]),
+            vector![c(1., 3.), c(0., 0.)]
+        );
+        assert!(m.norm().abs_diff_eq(&c(8f64.sqrt(), 0.), 1e-12));
+
+        // (1 + i) - 2 (-i) = 1 + 3i
+        assert!(m.determinant().abs_diff_eq(&c(1., 3.), 1e-12));
+
+        let inv = m.inverse().unwrap();
+        assert_matrix_approx_eq!(m.mul_mat(&inv), m.identity(), 1e-12);
+    }
+
+    #[test]
+    fn row_echelon_test() {
+        let m = matrix![
+            [c(1., 0.), c(0., 1.), c(2., 0.)],
+            [c(0., 1.), c(-1., 0.), c(0., 2.)]
+        ];
+        let r = m.row_echelon();
+
+        // Second row is i times the first
+        assert_eq!(
+            r,
+            matrix![
+                [c(1., 0.), c(0., 1.), c(2., 0.)],
+                [c(0., 0.), c(0., 0.), c(0., 0.)]
+            ]
+        );
+        assert_eq!(m.rank(), 1);
+    }
+}
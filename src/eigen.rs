@@ -0,0 +1,649 @@
+use super::FloatBound;
+use super::MatrixError;
+use super::VectorSpace;
+
+use super::matrix::Matrix;
+use super::vector::vector;
+use super::vector::Vector;
+
+use std::cmp::Ordering;
+
+const DEFAULT_MAX_SWEEPS: usize = 100;
+const DEFAULT_MAX_QR_ITERATIONS: usize = 100; // per deflated eigenvalue
+
+impl<K: FloatBound> Matrix<K> {
+    fn off_diagonal_norm(&self) -> K {
+        let n = self.n_rows();
+        let mut sum = K::ZERO;
+        for j in 0..n {
+            for i in 0..n {
+                if i != j {
+                    sum += &(self[(i, j)] * self[(i, j)]);
+                }
+            }
+        }
+        sum.sqrt()
+    }
+
+    fn jacobi_rotate(a: &mut Matrix<K>, v: &mut Matrix<K>, p: usize, q: usize) {
+        let n = a.n_rows();
+        let two = K::from_f64(2.);
+
+        let theta = (a[(q, q)] - a[(p, p)]) / (two * a[(p, q)]);
+        let mut t = K::ONE / (theta.abs() + (theta * theta + K::ONE).sqrt());
+        if theta < K::ZERO {
+            t = -t;
+        }
+        let c = K::ONE / (t * t + K::ONE).sqrt();
+        let s = t * c;
+
+        let apq = a[(p, q)];
+        a[(p, p)] -= &(t * apq);
+        a[(q, q)] += &(t * apq);
+        a[(p, q)] = K::ZERO;
+        a[(q, p)] = K::ZERO;
+
+        for k in 0..n {
+            if k != p && k != q {
+                let (akp, akq) = (a[(k, p)], a[(k, q)]);
+                a[(k, p)] = c * akp - s * akq;
+                a[(p, k)] = a[(k, p)];
+                a[(k, q)] = s * akp + c * akq;
+                a[(q, k)] = a[(k, q)];
+            }
+
+            let (vkp, vkq) = (v[(k, p)], v[(k, q)]);
+            v[(k, p)] = c * vkp - s * vkq;
+            v[(k, q)] = s * vkp + c * vkq;
+        }
+    }
+
+    pub fn symmetric_eigen(&self) -> Result<(Vector<K>, Matrix<K>), MatrixError> {
+        let tol = K::EPSILON * K::from_f64(self.n_rows() as f64);
+        self.symmetric_eigen_with(tol, DEFAULT_MAX_SWEEPS)
+    }
+
+    // Cyclic Jacobi: stops once the off-diagonal part falls under
+    // tol * ||A||, or fails after max_sweeps sweeps
+    pub fn symmetric_eigen_with(
+        &self,
+        tol: K,
+        max_sweeps: usize,
+    ) -> Result<(Vector<K>, Matrix<K>), MatrixError> {
+        self.check_symmetric("symmetric_eigen")?;
+
+        let n = self.n_rows();
+        let mut a = self.clone();
+        let mut v = self.identity();
+        let threshold = tol * self.norm();
+
+        let mut sweeps = 0;
+        while a.off_diagonal_norm() > threshold {
+            if sweeps == max_sweeps {
+                return Err(MatrixError::NoConvergence {
+                    iterations: max_sweeps,
+                });
+            }
+
+            for p in 0..n {
+                for q in (p + 1)..n {
+                    if !a[(p, q)].is_zero() {
+                        Self::jacobi_rotate(&mut a, &mut v, p, q);
+                    }
+                }
+            }
+            sweeps += 1;
+        }
+
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&i, &j| a[(i, i)].partial_cmp(&a[(j, j)]).unwrap_or(Ordering::Equal));
+
+        let mut values = vector![K::ZERO; n];
+        let mut vectors = self.identity();
+        for (k, &i) in order.iter().enumerate() {
+            values[k] = a[(i, i)];
+            for r in 0..n {
+                vectors[(r, k)] = v[(r, i)];
+            }
+        }
+
+        Ok((values, vectors))
+    }
+}
+
+impl<K: FloatBound> Matrix<K> {
+    // Orthogonal similarity A = Q H Q^T with H upper Hessenberg
+    pub fn hessenberg(&self) -> (Matrix<K>, Matrix<K>) {
+        match self.try_hessenberg() {
+            Ok(qh) => qh,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    pub fn try_hessenberg(&self) -> Result<(Matrix<K>, Matrix<K>), MatrixError> {
+        self.check_square("hessenberg")?;
+
+        let n = self.n_rows();
+        let mut h = self.clone();
+        let mut v = self.identity();
+        let mut ort = vector![K::ZERO; n];
+
+        if n < 3 {
+            return Ok((v, h));
+        }
+
+        for m in 1..(n - 1) {
+            let mut scale = K::ZERO;
+            for i in m..n {
+                scale += &h[(i, m - 1)].abs();
+            }
+            if scale.is_zero() {
+                continue;
+            }
+
+            let mut sq = K::ZERO;
+            for i in (m..n).rev() {
+                ort[i] = h[(i, m - 1)] / scale;
+                sq += &(ort[i] * ort[i]);
+            }
+            let mut g = sq.sqrt();
+            if ort[m] > K::ZERO {
+                g = -g;
+            }
+            sq -= &(ort[m] * g);
+            ort[m] -= &g;
+
+            for j in m..n {
+                let mut f = K::ZERO;
+                for i in (m..n).rev() {
+                    f += &(ort[i] * h[(i, j)]);
+                }
+                f /= &sq;
+                for i in m..n {
+                    h[(i, j)] -= &(f * ort[i]);
+                }
+            }
+
+            for i in 0..n {
+                let mut f = K::ZERO;
+                for j in (m..n).rev() {
+                    f += &(ort[j] * h[(i, j)]);
+                }
+                f /= &sq;
+                for j in m..n {
+                    h[(i, j)] -= &(f * ort[j]);
+                }
+            }
+
+            ort[m] *= &scale;
+            h[(m, m - 1)] = scale * g;
+        }
+
+        // Accumulate the reflectors into Q
+        for m in (1..(n - 1)).rev() {
+            if h[(m, m - 1)].is_zero() {
+                continue;
+            }
+            for i in (m + 1)..n {
+                ort[i] = h[(i, m - 1)];
+            }
+            for j in m..n {
+                let mut g = K::ZERO;
+                for i in m..n {
+                    g += &(ort[i] * v[(i, j)]);
+                }
+                g = (g / ort[m]) / h[(m, m - 1)];
+                for i in m..n {
+                    v[(i, j)] += &(g * ort[i]);
+                }
+            }
+        }
+
+        for j in 0..n {
+            for i in (j + 2)..n {
+                h[(i, j)] = K::ZERO;
+            }
+        }
+
+        Ok((v, h))
+    }
+
+    pub fn schur(&self) -> Result<(Matrix<K>, Matrix<K>), MatrixError> {
+        self.schur_with(K::EPSILON, DEFAULT_MAX_QR_ITERATIONS)
+    }
+
+    // Real Schur form A = Q T Q^T through Francis double-shift QR steps on
+    // the Hessenberg form. T is quasi upper triangular: complex conjugate
+    // eigenvalue pairs remain as 2x2 diagonal blocks.
+    pub fn schur_with(
+        &self,
+        tol: K,
+        max_iterations: usize,
+    ) -> Result<(Matrix<K>, Matrix<K>), MatrixError> {
+        let (mut v, mut h) = self.try_hessenberg()?;
+        let nn = self.n_rows();
+        let two = K::from_f64(2.);
+
+        let mut norm = K::ZERO;
+        for i in 0..nn {
+            for j in i.saturating_sub(1)..nn {
+                norm += &h[(i, j)].abs();
+            }
+        }
+
+        let mut exshift = K::ZERO;
+        let (mut p, mut q, mut r): (K, K, K);
+        let (mut s, mut z);
+        let (mut w, mut x, mut y);
+
+        let mut n = nn as isize - 1;
+        let mut iter = 0;
+
+        while n >= 0 {
+            let nu = n as usize;
+
+            // Look for a single small sub-diagonal element
+            let mut l = nu;
+            while l > 0 {
+                s = h[(l - 1, l - 1)].abs() + h[(l, l)].abs();
+                if s.is_zero() {
+                    s = norm;
+                }
+                if h[(l, l - 1)].abs() < tol * s {
+                    h[(l, l - 1)] = K::ZERO;
+                    break;
+                }
+                l -= 1;
+            }
+
+            if l == nu {
+                // One root found
+                h[(nu, nu)] += &exshift;
+                n -= 1;
+                iter = 0;
+            } else if l == nu - 1 {
+                // Two roots found
+                w = h[(nu, nu - 1)] * h[(nu - 1, nu)];
+                p = (h[(nu - 1, nu - 1)] - h[(nu, nu)]) / two;
+                q = p * p + w;
+                z = q.abs().sqrt();
+                h[(nu, nu)] += &exshift;
+                h[(nu - 1, nu - 1)] += &exshift;
+
+                if q >= K::ZERO {
+                    // Real pair: rotate the block into upper triangular form
+                    z = if p >= K::ZERO { p + z } else { p - z };
+                    x = h[(nu, nu - 1)];
+                    s = x.abs() + z.abs();
+                    p = x / s;
+                    q = z / s;
+                    r = (p * p + q * q).sqrt();
+                    p /= &r;
+                    q /= &r;
+
+                    for j in (nu - 1)..nn {
+                        z = h[(nu - 1, j)];
+                        h[(nu - 1, j)] = q * z + p * h[(nu, j)];
+                        h[(nu, j)] = q * h[(nu, j)] - p * z;
+                    }
+                    for i in 0..=nu {
+                        z = h[(i, nu - 1)];
+                        h[(i, nu - 1)] = q * z + p * h[(i, nu)];
+                        h[(i, nu)] = q * h[(i, nu)] - p * z;
+                    }
+                    for i in 0..nn {
+                        z = v[(i, nu - 1)];
+                        v[(i, nu - 1)] = q * z + p * v[(i, nu)];
+                        v[(i, nu)] = q * v[(i, nu)] - p * z;
+                    }
+                    h[(nu, nu - 1)] = K::ZERO;
+                }
+                n -= 2;
+                iter = 0;
+            } else {
+                // No convergence yet
+                if iter == max_iterations {
+                    return Err(MatrixError::NoConvergence {
+                        iterations: max_iterations,
+                    });
+                }
+
+                x = h[(nu, nu)];
+                y = h[(nu - 1, nu - 1)];
+                w = h[(nu, nu - 1)] * h[(nu - 1, nu)];
+
+                // Exceptional shifts (Wilkinson's and MATLAB's)
+                if iter == 10 {
+                    exshift += &x;
+                    for i in 0..=nu {
+                        h[(i, i)] -= &x;
+                    }
+                    s = h[(nu, nu - 1)].abs() + h[(nu - 1, nu - 2)].abs();
+                    x = K::from_f64(0.75) * s;
+                    y = x;
+                    w = K::from_f64(-0.4375) * s * s;
+                }
+                if iter == 30 {
+                    s = (y - x) / two;
+                    s = s * s + w;
+                    if s > K::ZERO {
+                        s = s.sqrt();
+                        if y < x {
+                            s = -s;
+                        }
+                        s = x - w / ((y - x) / two + s);
+                        for i in 0..=nu {
+                            h[(i, i)] -= &s;
+                        }
+                        exshift += &s;
+                        x = K::from_f64(0.964);
+                        y = x;
+                        w = x;
+                    }
+                }
+                iter += 1;
+
+                // Look for two consecutive small sub-diagonal elements
+                let mut m = nu - 2;
+                loop {
+                    z = h[(m, m)];
+                    r = x - z;
+                    s = y - z;
+                    p = (r * s - w) / h[(m + 1, m)] + h[(m, m + 1)];
+                    q = h[(m + 1, m + 1)] - z - r - s;
+                    r = h[(m + 2, m + 1)];
+                    s = p.abs() + q.abs() + r.abs();
+                    p /= &s;
+                    q /= &s;
+                    r /= &s;
+                    if m == l {
+                        break;
+                    }
+                    if h[(m, m - 1)].abs() * (q.abs() + r.abs())
+                        < tol
+                            * (p.abs()
+                                * (h[(m - 1, m - 1)].abs() + z.abs() + h[(m + 1, m + 1)].abs()))
+                    {
+                        break;
+                    }
+                    m -= 1;
+                }
+
+                for i in (m + 2)..=nu {
+                    h[(i, i - 2)] = K::ZERO;
+                    if i > m + 2 {
+                        h[(i, i - 3)] = K::ZERO;
+                    }
+                }
+
+                // Double QR step on rows l..=n and columns m..=n
+                for k in m..nu {
+                    let notlast = k != nu - 1;
+                    if k != m {
+                        p = h[(k, k - 1)];
+                        q = h[(k + 1, k - 1)];
+                        r = if notlast { h[(k + 2, k - 1)] } else { K::ZERO };
+                        x = p.abs() + q.abs() + r.abs();
+                        if x.is_zero() {
+                            continue;
+                        }
+                        p /= &x;
+                        q /= &x;
+                        r /= &x;
+                    }
+
+                    s = (p * p + q * q + r * r).sqrt();
+                    if p < K::ZERO {
+                        s = -s;
+                    }
+                    if s.is_zero() {
+                        continue;
+                    }
+
+                    if k != m {
+                        h[(k, k - 1)] = -s * x;
+                    } else if l != m {
+                        h[(k, k - 1)] = -h[(k, k - 1)];
+                    }
+                    p += &s;
+                    x = p / s;
+                    y = q / s;
+                    z = r / s;
+                    q /= &p;
+                    r /= &p;
+
+                    for j in k..nn {
+                        p = h[(k, j)] + q * h[(k + 1, j)];
+                        if notlast {
+                            p += &(r * h[(k + 2, j)]);
+                            h[(k + 2, j)] -= &(p * z);
+                        }
+                        h[(k, j)] -= &(p * x);
+                        h[(k + 1, j)] -= &(p * y);
+                    }
+                    for i in 0..=nu.min(k + 3) {
+                        p = x * h[(i, k)] + y * h[(i, k + 1)];
+                        if notlast {
+                            p += &(z * h[(i, k + 2)]);
+                            h[(i, k + 2)] -= &(p * r);
+                        }
+                        h[(i, k)] -= &p;
+                        h[(i, k + 1)] -= &(p * q);
+                    }
+                    for i in 0..nn {
+                        p = x * v[(i, k)] + y * v[(i, k + 1)];
+                        if notlast {
+                            p += &(z * v[(i, k + 2)]);
+                            v[(i, k + 2)] -= &(p * r);
+                        }
+                        v[(i, k)] -= &p;
+                        v[(i, k + 1)] -= &(p * q);
+                    }
+                }
+            }
+        }
+
+        // Entries below the sub-diagonal are implicitly zero after the
+        // bulge chasing, clear what is left of them
+        for j in 0..nn {
+            for i in (j + 2)..nn {
+                h[(i, j)] = K::ZERO;
+            }
+        }
+
+        Ok((v, h))
+    }
+
+    pub fn eigenvalues(&self) -> Result<Vec<(K, K)>, MatrixError> {
+        self.eigenvalues_with(K::EPSILON, DEFAULT_MAX_QR_ITERATIONS)
+    }
+
+    // Eigenvalues as (real, imaginary) pairs, in the order they appear on
+    // the diagonal of the real Schur form
+    pub fn eigenvalues_with(
+        &self,
+        tol: K,
+        max_iterations: usize,
+    ) -> Result<Vec<(K, K)>, MatrixError> {
+        let (_, t) = self.schur_with(tol, max_iterations)?;
+        let n = t.n_rows();
+        let two = K::from_f64(2.);
+        let mut values = Vec::with_capacity(n);
+
+        let mut i = 0;
+        while i < n {
+            if i + 1 < n && !t[(i + 1, i)].is_zero() {
+                // Complex pair from the 2x2 block [[a, b], [c, d]]
+                let (a, b, c, d) = (t[(i, i)], t[(i, i + 1)], t[(i + 1, i)], t[(i + 1, i + 1)]);
+                let re = (a + d) / two;
+                let disc = (a - d) * (a - d) / (two * two) + b * c;
+                let im = (-disc).abs().sqrt();
+                values.push((re, im));
+                values.push((re, -im));
+                i += 2;
+            } else {
+                values.push((t[(i, i)], K::ZERO));
+                i += 1;
+            }
+        }
+
+        Ok(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::matrix::matrix;
+
+    fn assert_eigenpairs(a: &Matrix<f64>, values: &Vector<f64>, vectors: &Matrix<f64>) {
+        for k in 0..values.len() {
+            let mut col = vector![0.; a.n_rows()];
+            for i in 0..a.n_rows() {
+                col[i] = vectors[(i, k)];
+            }
+
+            let mut lhs = a.mul_vec(&col);
+            col.scl(values[k]);
+            lhs.sub(&col);
+            assert!(lhs.norm() < 1e-10);
+        }
+
+        let mut d = vectors.transpose().mul_mat(vectors);
+        d.sub(&a.identity());
+        assert!(d.norm() < 1e-12);
+    }
+
+    #[test]
+    fn symmetric_eigen_test() {
+        let a = matrix![[2., -1., 0.], [-1., 2., -1.], [0., -1., 2.]];
+        let (values, vectors) = a.symmetric_eigen().unwrap();
+
+        let s = 2f64.sqrt();
+        let expected = [2. - s, 2., 2. + s];
+        for k in 0..3 {
+            assert!((values[k] - expected[k]).abs() < 1e-12);
+        }
+        assert_eigenpairs(&a, &values, &vectors);
+    }
+
+    #[test]
+    fn symmetric_eigen_sorted_test() {
+        let a: Matrix<f64> = matrix![
+            [4., 1., -2., 2.],
+            [1., 2., 0., 1.],
+            [-2., 0., 3., -2.],
+            [2., 1., -2., -1.]
+        ];
+        let (values, vectors) = a.symmetric_eigen().unwrap();
+
+        for k in 1..4 {
+            assert!(values[k - 1] <= values[k]);
+        }
+        assert!((values.sum() - a.trace()).abs() < 1e-12);
+        assert_eigenpairs(&a, &values, &vectors);
+
+        let (values, _) = matrix![[3., 0.], [0., -1.]].symmetric_eigen().unwrap();
+
+        assert_eq!(values, vector![-1., 3.]);
+    }
+
+    fn assert_similar(a: &Matrix<f64>, q: &Matrix<f64>, t: &Matrix<f64>) {
+        let mut d = q.mul_mat(t).mul_mat(&q.transpose());
+        d.sub(a);
+        assert!(d.norm() < 1e-10);
+
+        let mut d = q.transpose().mul_mat(q);
+        d.sub(&a.identity());
+        assert!(d.norm() < 1e-12);
+    }
+
+    #[test]
+    fn hessenberg_test() {
+        let a = matrix![
+            [4., 1., -2., 2.],
+            [1., 2., 0., 1.],
+            [-2., 0., 3., -2.],
+            [7., 1., -2., -1.]
+        ];
+        let (q, h) = a.hessenberg();
+
+        assert_similar(&a, &q, &h);
+        for j in 0..4 {
+            for i in (j + 2)..4 {
+                assert_eq!(h[(i, j)], 0.);
+            }
+        }
+    }
+
+    #[test]
+    fn schur_test() {
+        let a = matrix![
+            [1., 2., 3., 4.],
+            [-2., 1., 0., 5.],
+            [0., 3., -1., 2.],
+            [1., 0., 2., 3.]
+        ];
+        let (q, t) = a.schur().unwrap();
+
+        assert_similar(&a, &q, &t);
+        for j in 0..4 {
+            for i in (j + 2)..4 {
+                assert_eq!(t[(i, j)], 0.);
+            }
+        }
+        // No two consecutive non-zero sub-diagonal entries
+        for i in 1..3 {
+            assert!(t[(i, i - 1)] == 0. || t[(i + 1, i)] == 0.);
+        }
+    }
+
+    #[test]
+    fn eigenvalues_test() {
+        // Rotation-like block: eigenvalues 1 ± 2i, and 3
+        let a: Matrix<f64> = matrix![[1., -2., 0.], [2., 1., 0.], [0., 0., 3.]];
+        let mut values = a.eigenvalues().unwrap();
+        values.sort_by(|x, y| x.partial_cmp(y).unwrap());
+
+        let expected = [(1., -2.), (1., 2.), (3., 0.)];
+        for (v, e) in values.iter().zip(expected.iter()) {
+            assert!((v.0 - e.0).abs() < 1e-12 && (v.1 - e.1).abs() < 1e-12);
+        }
+
+        // Companion matrix of (x - 1)(x - 2)(x - 3)
+        let c = matrix![[6., -11., 6.], [1., 0., 0.], [0., 1., 0.]];
+        let mut values = c.eigenvalues().unwrap();
+        values.sort_by(|x, y| x.partial_cmp(y).unwrap());
+
+        for (k, v) in values.iter().enumerate() {
+            assert!((v.0 - (k + 1) as f64).abs() < 1e-10 && v.1 == 0.);
+        }
+    }
+
+    #[test]
+    fn eigenvalues_error_test() {
+        let a = matrix![[0., 1.], [-1., 0.]];
+        let b = matrix![[1., 2., 3.], [4., 5., 6.], [7., 8., 10.]];
+
+        assert_eq!(a.eigenvalues().unwrap(), vec![(0., 1.), (0., -1.)]);
+        assert_eq!(
+            b.eigenvalues_with(1e-16, 0).unwrap_err(),
+            MatrixError::NoConvergence { iterations: 0 }
+        );
+    }
+
+    #[test]
+    fn symmetric_eigen_error_test() {
+        let a = matrix![[4., 1., -2.], [1., 2., 0.], [-2., 0., 3.]];
+
+        assert_eq!(
+            a.symmetric_eigen_with(1e-15, 0).unwrap_err(),
+            MatrixError::NoConvergence { iterations: 0 }
+        );
+        assert_eq!(
+            matrix![[1., 2.], [0., 1.]].symmetric_eigen().unwrap_err(),
+            MatrixError::NotSymmetric
+        );
+    }
+}
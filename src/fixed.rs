@@ -0,0 +1,535 @@
+use super::Dimension;
+use super::FieldBound;
+use super::MatrixError;
+use super::VectorSpace;
+use super::D2;
+
+use super::matrix::Matrix;
+use super::vector::Vector;
+
+use std::fmt;
+use std::ops;
+
+// Stack-allocated counterparts of Vector and Matrix. Shapes are part of the
+// type, so mismatches are rejected at compile time instead of at run time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SVector<K: FieldBound + Copy, const N: usize> {
+    fields: [K; N],
+}
+
+// Column-major like Matrix: data[j][i] is the entry (i, j)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SMatrix<K: FieldBound + Copy, const R: usize, const C: usize> {
+    data: [[K; R]; C],
+}
+
+pub type Vec2<K> = SVector<K, 2>;
+pub type Vec3<K> = SVector<K, 3>;
+pub type Vec4<K> = SVector<K, 4>;
+
+pub type Mat2<K> = SMatrix<K, 2, 2>;
+pub type Mat3<K> = SMatrix<K, 3, 3>;
+pub type Mat4<K> = SMatrix<K, 4, 4>;
+
+// SVector
+
+impl<K: FieldBound + Copy, const N: usize> SVector<K, N> {
+    pub const fn new(fields: [K; N]) -> Self {
+        SVector { fields }
+    }
+
+    pub fn zeros() -> Self {
+        SVector {
+            fields: [K::ZERO; N],
+        }
+    }
+
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, K> {
+        self.fields.iter()
+    }
+
+    pub fn as_array(&self) -> &[K; N] {
+        &self.fields
+    }
+
+    pub fn scl(&mut self, a: K) {
+        for k in &mut self.fields {
+            *k *= &a;
+        }
+    }
+
+    // Conjugate-linear in self, as Vector::dot
+    pub fn dot(&self, v: &Self) -> K {
+        let mut sum = K::ZERO;
+        for (a, b) in self.fields.iter().zip(v.fields.iter()) {
+            sum += &(a.conjugate() * *b);
+        }
+        sum
+    }
+
+    pub fn sum(&self) -> K {
+        let mut sum = K::ZERO;
+        for k in &self.fields {
+            sum += k;
+        }
+        sum
+    }
+
+    pub fn norm(&self) -> K {
+        self.dot(self).sqrt()
+    }
+
+    pub fn norm_1(&self) -> K {
+        let mut sum = K::ZERO;
+        for k in &self.fields {
+            sum += &k.abs();
+        }
+        sum
+    }
+
+    pub fn norm_inf(&self) -> K {
+        let mut max = K::ZERO;
+        for k in &self.fields {
+            if k.abs() > max {
+                max = k.abs();
+            }
+        }
+        max
+    }
+
+    pub fn lerp(&self, v: &Self, t: K) -> Self {
+        *self + (*v - *self) * t
+    }
+}
+
+impl<K: FieldBound + Copy> SVector<K, 3> {
+    pub fn cross_product(&self, v: &Self) -> Self {
+        let (u, v) = (&self.fields, &v.fields);
+        SVector::new([
+            u[1] * v[2] - u[2] * v[1],
+            u[2] * v[0] - u[0] * v[2],
+            u[0] * v[1] - u[1] * v[0],
+        ])
+    }
+}
+
+impl<K: FieldBound + Copy, const N: usize> From<[K; N]> for SVector<K, N> {
+    fn from(fields: [K; N]) -> Self {
+        SVector { fields }
+    }
+}
+
+impl<K: FieldBound + Copy, const N: usize> From<SVector<K, N>> for Vector<K> {
+    fn from(v: SVector<K, N>) -> Self {
+        Vector::from(v.fields.as_slice())
+    }
+}
+
+impl<K: FieldBound + Copy, const N: usize> TryFrom<&Vector<K>> for SVector<K, N> {
+    type Error = MatrixError;
+
+    fn try_from(v: &Vector<K>) -> Result<Self, Self::Error> {
+        if v.len() != N {
+            return Err(MatrixError::size("SVector::try_from", N, v.len()));
+        }
+
+        let mut fields = [K::ZERO; N];
+        fields.copy_from_slice(v.slice(0..N));
+        Ok(SVector { fields })
+    }
+}
+
+impl<K: FieldBound + Copy, const N: usize> ops::Index<usize> for SVector<K, N> {
+    type Output = K;
+    fn index(&self, i: usize) -> &K {
+        &self.fields[i]
+    }
+}
+
+impl<K: FieldBound + Copy, const N: usize> ops::IndexMut<usize> for SVector<K, N> {
+    fn index_mut(&mut self, i: usize) -> &mut K {
+        &mut self.fields[i]
+    }
+}
+
+impl<K: FieldBound + Copy, const N: usize> fmt::Display for SVector<K, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", Vector::from(*self))
+    }
+}
+
+impl<K: FieldBound + Copy, const N: usize> ops::Neg for SVector<K, N> {
+    type Output = Self;
+    fn neg(mut self) -> Self {
+        for k in &mut self.fields {
+            *k = -*k;
+        }
+        self
+    }
+}
+
+macro_rules! impl_svector_ops {
+    ($trait:ident, $fun:ident, $trait_assign:ident, $fun_assign:ident, $op:tt) => {
+        impl<K: FieldBound + Copy, const N: usize> ops::$trait_assign for SVector<K, N> {
+            fn $fun_assign(&mut self, other: Self) {
+                for (a, b) in self.fields.iter_mut().zip(other.fields.iter()) {
+                    *a $op b;
+                }
+            }
+        }
+
+        impl<K: FieldBound + Copy, const N: usize> ops::$trait for SVector<K, N> {
+            type Output = Self;
+            fn $fun(mut self, other: Self) -> Self {
+                self $op other;
+                self
+            }
+        }
+    };
+}
+
+impl_svector_ops!(Add, add, AddAssign, add_assign, +=);
+impl_svector_ops!(Sub, sub, SubAssign, sub_assign, -=);
+
+impl<K: FieldBound + Copy, const N: usize> ops::Mul<K> for SVector<K, N> {
+    type Output = Self;
+    fn mul(mut self, a: K) -> Self {
+        self.scl(a);
+        self
+    }
+}
+
+// SMatrix
+
+impl<K: FieldBound + Copy, const R: usize, const C: usize> SMatrix<K, R, C> {
+    // Row-major literal, as in matrix![[..], [..]]
+    pub fn new(rows: [[K; C]; R]) -> Self {
+        let mut data = [[K::ZERO; R]; C];
+        for (i, row) in rows.iter().enumerate() {
+            for (j, k) in row.iter().enumerate() {
+                data[j][i] = *k;
+            }
+        }
+        SMatrix { data }
+    }
+
+    pub fn from_cols(cols: [SVector<K, R>; C]) -> Self {
+        SMatrix {
+            data: cols.map(|c| c.fields),
+        }
+    }
+
+    pub fn zeros() -> Self {
+        SMatrix {
+            data: [[K::ZERO; R]; C],
+        }
+    }
+
+    pub const fn n_rows(&self) -> usize {
+        R
+    }
+
+    pub const fn n_cols(&self) -> usize {
+        C
+    }
+
+    pub fn shape(&self) -> Dimension {
+        Dimension::D2(D2 { rows: R, cols: C })
+    }
+
+    pub fn col(&self, j: usize) -> SVector<K, R> {
+        SVector::new(self.data[j])
+    }
+
+    pub fn row(&self, i: usize) -> SVector<K, C> {
+        SVector::new(self.data.map(|c| c[i]))
+    }
+
+    pub fn transpose(&self) -> SMatrix<K, C, R> {
+        let mut data = [[K::ZERO; C]; R];
+        for (j, col) in self.data.iter().enumerate() {
+            for (i, k) in col.iter().enumerate() {
+                data[i][j] = *k;
+            }
+        }
+        SMatrix { data }
+    }
+
+    pub fn scl(&mut self, a: K) {
+        for k in self.data.iter_mut().flatten() {
+            *k *= &a;
+        }
+    }
+
+    pub fn norm(&self) -> K {
+        let mut sqsum = K::ZERO;
+        for k in self.data.iter().flatten() {
+            sqsum += &(k.conjugate() * *k);
+        }
+        sqsum.sqrt()
+    }
+
+    pub fn mul_vec(&self, v: &SVector<K, C>) -> SVector<K, R> {
+        let mut y = [K::ZERO; R];
+        for (col, v_j) in self.data.iter().zip(v.fields.iter()) {
+            for (y_i, a_ij) in y.iter_mut().zip(col.iter()) {
+                *y_i += &(*a_ij * *v_j);
+            }
+        }
+        SVector::new(y)
+    }
+
+    pub fn mul_mat<const P: usize>(&self, m: &SMatrix<K, C, P>) -> SMatrix<K, R, P> {
+        SMatrix {
+            data: m.data.map(|col| self.mul_vec(&SVector::new(col)).fields),
+        }
+    }
+}
+
+impl<K: FieldBound + Copy, const N: usize> SMatrix<K, N, N> {
+    pub fn identity() -> Self {
+        let mut m = Self::zeros();
+        for i in 0..N {
+            m.data[i][i] = K::ONE;
+        }
+        m
+    }
+
+    pub fn trace(&self) -> K {
+        let mut sum = K::ZERO;
+        for i in 0..N {
+            sum += &self.data[i][i];
+        }
+        sum
+    }
+
+    // Fraction-free (Bareiss) elimination on a stack copy, exact for integers
+    pub fn determinant(&self) -> K {
+        let mut a = self.transpose().data;
+        let mut sign = K::ONE;
+        let mut prev = K::ONE;
+
+        for k in 0..N {
+            if a[k][k].is_zero() {
+                match (k + 1..N).find(|&i| !a[i][k].is_zero()) {
+                    Some(i) => {
+                        a.swap(k, i);
+                        sign = -sign;
+                    }
+                    None => return K::ZERO,
+                }
+            }
+            for i in k + 1..N {
+                for j in k + 1..N {
+                    a[i][j] = (a[i][j] * a[k][k] - a[i][k] * a[k][j]) / prev;
+                }
+            }
+            prev = a[k][k];
+        }
+
+        if N == 0 {
+            return K::ONE;
+        }
+        sign * a[N - 1][N - 1]
+    }
+}
+
+impl<K: FieldBound + Copy, const R: usize, const C: usize> From<SMatrix<K, R, C>> for Matrix<K> {
+    fn from(m: SMatrix<K, R, C>) -> Self {
+        Matrix::from_col_major(R, C, m.data.iter().flatten().copied().collect())
+    }
+}
+
+impl<K: FieldBound + Copy, const R: usize, const C: usize> TryFrom<&Matrix<K>>
+    for SMatrix<K, R, C>
+{
+    type Error = MatrixError;
+
+    fn try_from(m: &Matrix<K>) -> Result<Self, Self::Error> {
+        if m.n_rows() != R || m.n_cols() != C {
+            return Err(MatrixError::incompatible(
+                "SMatrix::try_from",
+                Dimension::D2(D2 { rows: R, cols: C }),
+                m.shape(),
+            ));
+        }
+
+        let mut data = [[K::ZERO; R]; C];
+        for (j, col) in data.iter_mut().enumerate() {
+            col.copy_from_slice(m.col(j));
+        }
+        Ok(SMatrix { data })
+    }
+}
+
+impl<K: FieldBound + Copy, const R: usize, const C: usize> ops::Index<(usize, usize)>
+    for SMatrix<K, R, C>
+{
+    type Output = K;
+    fn index(&self, (i, j): (usize, usize)) -> &K {
+        &self.data[j][i]
+    }
+}
+
+impl<K: FieldBound + Copy, const R: usize, const C: usize> ops::IndexMut<(usize, usize)>
+    for SMatrix<K, R, C>
+{
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut K {
+        &mut self.data[j][i]
+    }
+}
+
+impl<K: FieldBound + Copy, const R: usize, const C: usize> fmt::Display for SMatrix<K, R, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", Matrix::from(*self))
+    }
+}
+
+impl<K: FieldBound + Copy, const R: usize, const C: usize> ops::Add for SMatrix<K, R, C> {
+    type Output = Self;
+    fn add(mut self, other: Self) -> Self {
+        for (a, b) in self
+            .data
+            .iter_mut()
+            .flatten()
+            .zip(other.data.iter().flatten())
+        {
+            *a += b;
+        }
+        self
+    }
+}
+
+impl<K: FieldBound + Copy, const R: usize, const C: usize> ops::Sub for SMatrix<K, R, C> {
+    type Output = Self;
+    fn sub(mut self, other: Self) -> Self {
+        for (a, b) in self
+            .data
+            .iter_mut()
+            .flatten()
+            .zip(other.data.iter().flatten())
+        {
+            *a -= b;
+        }
+        self
+    }
+}
+
+impl<K: FieldBound + Copy, const R: usize, const C: usize> ops::Mul<SVector<K, C>>
+    for SMatrix<K, R, C>
+{
+    type Output = SVector<K, R>;
+    fn mul(self, v: SVector<K, C>) -> SVector<K, R> {
+        self.mul_vec(&v)
+    }
+}
+
+impl<K: FieldBound + Copy, const R: usize, const C: usize, const P: usize>
+    ops::Mul<SMatrix<K, C, P>> for SMatrix<K, R, C>
+{
+    type Output = SMatrix<K, R, P>;
+    fn mul(self, m: SMatrix<K, C, P>) -> SMatrix<K, R, P> {
+        self.mul_mat(&m)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::matrix::matrix;
+    use crate::vector::vector;
+
+    #[test]
+    fn svector_test() {
+        let u = Vec3::new([1, 2, 3]);
+        let v = Vec3::new([4, 5, 6]);
+
+        assert_eq!(u + v, Vec3::new([5, 7, 9]));
+        assert_eq!(v - u, Vec3::new([3, 3, 3]));
+        assert_eq!(-u * 2, Vec3::new([-2, -4, -6]));
+        assert_eq!(u.dot(&v), 32);
+        assert_eq!(u.cross_product(&v), Vec3::new([-3, 6, -3]));
+        assert_eq!(u.norm_1(), 6);
+        assert_eq!(u.norm_inf(), 3);
+        assert_eq!(Vec2::new([3., 4.]).norm(), 5.);
+        assert_eq!(
+            Vec2::new([2., 1.]).lerp(&Vec2::new([4., 2.]), 0.5),
+            Vec2::new([3., 1.5])
+        );
+        assert_eq!(format!("{}", u), "[1, 2, 3]");
+    }
+
+    #[test]
+    fn smatrix_test() {
+        let a = Mat2::new([[3, -5], [6, 8]]);
+        let b = Mat2::new([[2, 1], [4, 2]]);
+
+        assert_eq!(a * b, Mat2::new([[-14, -7], [44, 22]]));
+        assert_eq!(a[(1, 0)], 6);
+        assert_eq!(a.trace(), 11);
+        assert_eq!(a.determinant(), 54);
+        assert_eq!(a.transpose(), Mat2::new([[3, 6], [-5, 8]]));
+        assert_eq!(a * Mat2::identity(), a);
+        assert_eq!(a.row(1), Vec2::new([6, 8]));
+        assert_eq!(a.col(1), Vec2::new([-5, 8]));
+
+        // 2x3 times 3x1, checked by the type system
+        let r = SMatrix::new([[1, 2, 3], [4, 5, 6]]);
+        let c = SMatrix::new([[1], [0], [-1]]);
+        assert_eq!(r * c, SMatrix::new([[-2], [-2]]));
+        assert_eq!(r * Vec3::new([1, 1, 1]), Vec2::new([6, 15]));
+    }
+
+    #[test]
+    fn determinant_test() {
+        let m = Mat3::new([[2, -5, 0], [4, 3, 7], [-2, 3, 4]]);
+        assert_eq!(m.determinant(), Matrix::from(m).determinant());
+
+        // Zero leading pivot needs a row swap
+        let p = Mat3::new([[0, 1, 2], [1, 0, 3], [4, -3, 8]]);
+        assert_eq!(p.determinant(), -2);
+
+        let s = Mat4::new([
+            [8., 5., -2., 4.],
+            [4., 2.5, 20., 4.],
+            [8., 5., 1., 4.],
+            [28., -4., 17., 1.],
+        ]);
+        assert_eq!(s.determinant(), 1032.);
+        assert_eq!(
+            Mat3::new([[1, 2, 3], [2, 4, 6], [0, 1, 1]]).determinant(),
+            0
+        );
+    }
+
+    #[test]
+    fn conversion_test() {
+        let v = vector![1., 2., 3.];
+        let s = Vec3::try_from(&v).unwrap();
+        assert_eq!(Vector::from(s), v);
+        assert!(Vec4::try_from(&v).is_err());
+
+        let m = matrix![[1, 2, 3], [4, 5, 6]];
+        let s = SMatrix::<i32, 2, 3>::try_from(&m).unwrap();
+        assert_eq!(s[(1, 2)], 6);
+        assert_eq!(Matrix::from(s), m);
+        assert_eq!(Matrix::from(s.transpose()), m.transpose());
+        assert_eq!(
+            Mat3::<i32>::try_from(&m),
+            Err(MatrixError::incompatible(
+                "SMatrix::try_from",
+                Dimension::D2(D2 { rows: 3, cols: 3 }),
+                m.shape(),
+            ))
+        );
+    }
+}
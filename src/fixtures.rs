@@ -0,0 +1,33 @@
+// Matrices shared by the test modules
+
+use super::matrix::matrix;
+use super::matrix::Matrix;
+use super::sparse::{CooMatrix, CsrMatrix};
+
+// Distinct entries, so every block of it is recognizable
+pub fn sample() -> Matrix<i32> {
+    matrix![
+        [1, 2, 3, 4],
+        [5, 6, 7, 8],
+        [9, 10, 11, 12],
+        [13, 14, 15, 16]
+    ]
+}
+
+// Mostly zeros, for the sparse formats
+pub fn sparse_sample() -> Matrix<i32> {
+    matrix![[4, 0, 0, -1], [0, 0, 2, 0], [1, 3, 0, 0]]
+}
+
+// 1D Poisson matrix tridiag(-1, 2, -1), symmetric positive definite
+pub fn poisson(n: usize) -> CsrMatrix<f64> {
+    let mut coo = CooMatrix::new(n, n);
+    for i in 0..n {
+        coo.push(i, i, 2.);
+        if i > 0 {
+            coo.push(i, i - 1, -1.);
+            coo.push(i - 1, i, -1.);
+        }
+    }
+    coo.to_csr()
+}
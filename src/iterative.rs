@@ -0,0 +1,664 @@
+use super::Dimension;
+use super::FieldBound;
+use super::FloatBound;
+use super::MatrixError;
+use super::VectorSpace;
+use super::D2;
+
+use super::matrix::Matrix;
+use super::preconditioner::{IdentityPreconditioner, Preconditioner};
+use super::sparse::{CscMatrix, CsrMatrix};
+use super::vector::vector;
+use super::vector::Vector;
+
+use std::error::Error;
+use std::fmt;
+
+// Anything that can be applied to a vector. The Krylov solvers only need the
+// product A x, so operators do not have to be stored as matrices.
+pub trait LinearOperator<K: FieldBound> {
+    fn n_rows(&self) -> usize;
+    fn n_cols(&self) -> usize;
+    fn apply(&self, x: &Vector<K>) -> Vector<K>;
+}
+
+impl<K: FieldBound> LinearOperator<K> for Matrix<K> {
+    fn n_rows(&self) -> usize {
+        Matrix::n_rows(self)
+    }
+
+    fn n_cols(&self) -> usize {
+        Matrix::n_cols(self)
+    }
+
+    fn apply(&self, x: &Vector<K>) -> Vector<K> {
+        self.mul_vec(x)
+    }
+}
+
+impl<K: FieldBound> LinearOperator<K> for CsrMatrix<K> {
+    fn n_rows(&self) -> usize {
+        CsrMatrix::n_rows(self)
+    }
+
+    fn n_cols(&self) -> usize {
+        CsrMatrix::n_cols(self)
+    }
+
+    fn apply(&self, x: &Vector<K>) -> Vector<K> {
+        self.mul_vec(x)
+    }
+}
+
+impl<K: FieldBound> LinearOperator<K> for CscMatrix<K> {
+    fn n_rows(&self) -> usize {
+        CscMatrix::n_rows(self)
+    }
+
+    fn n_cols(&self) -> usize {
+        CscMatrix::n_cols(self)
+    }
+
+    fn apply(&self, x: &Vector<K>) -> Vector<K> {
+        self.mul_vec(x)
+    }
+}
+
+// Relative residual norms ||b - A x_k|| / ||b||, starting with the initial
+// guess, so residuals().len() == iterations() + 1
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConvergenceReport<K: FloatBound> {
+    iterations: usize,
+    residuals: Vec<K>,
+    spectral_radii: Vec<Option<K>>,
+}
+
+impl<K: FloatBound> ConvergenceReport<K> {
+    pub(crate) fn new(initial: K) -> Self {
+        ConvergenceReport {
+            iterations: 0,
+            residuals: vec![initial],
+            spectral_radii: vec![],
+        }
+    }
+
+    pub(crate) fn push(&mut self, residual: K) {
+        self.iterations += 1;
+        self.residuals.push(residual);
+    }
+
+    // A stationary sweep, with the spectral radius estimated from it if any
+    pub(crate) fn push_sweep(&mut self, residual: K, rho: Option<K>) {
+        self.push(residual);
+        self.spectral_radii.push(rho);
+    }
+
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    pub fn residuals(&self) -> &[K] {
+        &self.residuals
+    }
+
+    pub fn residual(&self) -> K {
+        self.residuals[self.residuals.len() - 1]
+    }
+
+    // Estimates of the iteration matrix's spectral radius after each sweep,
+    // stationary methods only. The first sweep never has one.
+    pub fn spectral_radii(&self) -> &[Option<K>] {
+        &self.spectral_radii
+    }
+
+    // Latest of the estimates above
+    pub fn spectral_radius(&self) -> Option<K> {
+        self.spectral_radii.iter().rev().find_map(|rho| *rho)
+    }
+}
+
+// Failures of the iterative solvers. Shape errors and other failures shared
+// with the direct methods come as MatrixError; the rest carry the solver's
+// convergence report.
+#[derive(Debug, Clone, PartialEq)]
+pub enum IterativeError<K: FloatBound> {
+    Matrix(MatrixError),
+    // A division by zero in the recurrence, e.g. p^T A p <= 0 in CG
+    Breakdown { iterations: usize },
+    NoConvergence(ConvergenceReport<K>),
+    Diverged(ConvergenceReport<K>),
+}
+
+impl<K: FloatBound> fmt::Display for IterativeError<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IterativeError::Matrix(e) => write!(f, "{}", e),
+            IterativeError::Breakdown { iterations } => {
+                write!(f, "solver broke down after {} iterations", iterations)
+            }
+            IterativeError::NoConvergence(report) => write!(
+                f,
+                "no convergence after {} iterations, relative residual {}",
+                report.iterations(),
+                report.residual()
+            ),
+            IterativeError::Diverged(report) => write!(
+                f,
+                "diverged after {} iterations, relative residual {}",
+                report.iterations(),
+                report.residual()
+            ),
+        }
+    }
+}
+
+impl<K: FloatBound> Error for IterativeError<K> {}
+
+impl<K: FloatBound> From<MatrixError> for IterativeError<K> {
+    fn from(e: MatrixError) -> Self {
+        IterativeError::Matrix(e)
+    }
+}
+
+pub type Solution<K> = Result<(Vector<K>, ConvergenceReport<K>), IterativeError<K>>;
+
+pub(crate) fn default_tolerance<K: FloatBound>() -> K {
+    K::EPSILON.sqrt()
+}
+
+pub(crate) fn default_max_iterations(n: usize) -> usize {
+    10 * n.max(1)
+}
+
+pub(crate) fn check_system<K, A>(
+    operation: &'static str,
+    a: &A,
+    b: &Vector<K>,
+) -> Result<(), MatrixError>
+where
+    K: FloatBound,
+    A: LinearOperator<K> + ?Sized,
+{
+    if a.n_rows() != a.n_cols() {
+        return Err(MatrixError::NotSquare {
+            operation,
+            shape: Dimension::D2(D2 {
+                rows: a.n_rows(),
+                cols: a.n_cols(),
+            }),
+        });
+    }
+    if b.len() != a.n_rows() {
+        return Err(MatrixError::size(operation, a.n_rows(), b.len()));
+    }
+    Ok(())
+}
+
+// y += a x
+pub(crate) fn axpy<K: FloatBound>(y: &mut Vector<K>, a: K, x: &Vector<K>) {
+    for (y_i, x_i) in y.into_iter().zip(x.iter()) {
+        *y_i += &(a * *x_i);
+    }
+}
+
+pub(crate) fn finish<K: FloatBound>(
+    x: Vector<K>,
+    report: ConvergenceReport<K>,
+    tol: K,
+) -> Solution<K> {
+    if report.residual() <= tol {
+        Ok((x, report))
+    } else {
+        Err(IterativeError::NoConvergence(report))
+    }
+}
+
+// Conjugate gradient, for symmetric positive definite A
+pub fn cg<K, A>(a: &A, b: &Vector<K>) -> Solution<K>
+where
+    K: FloatBound,
+    A: LinearOperator<K> + ?Sized,
+{
+    cg_with(a, b, default_tolerance(), default_max_iterations(b.len()))
+}
+
+pub fn cg_with<K, A>(a: &A, b: &Vector<K>, tol: K, max_iter: usize) -> Solution<K>
+where
+    K: FloatBound,
+    A: LinearOperator<K> + ?Sized,
+{
+    cg_preconditioned(a, b, &IdentityPreconditioner, tol, max_iter)
+}
+
+// M must be symmetric positive definite as well
+pub fn cg_preconditioned<K, A, M>(
+    a: &A,
+    b: &Vector<K>,
+    m: &M,
+    tol: K,
+    max_iter: usize,
+) -> Solution<K>
+where
+    K: FloatBound,
+    A: LinearOperator<K> + ?Sized,
+    M: Preconditioner<K> + ?Sized,
+{
+    check_system("cg", a, b)?;
+
+    let mut x = vector![K::ZERO; b.len()];
+    let b_norm = b.norm();
+    let mut report = ConvergenceReport::new(K::ONE);
+    if b_norm.is_zero() {
+        report.residuals[0] = K::ZERO;
+        return Ok((x, report));
+    }
+
+    let mut r = b.clone();
+    let mut z = m.apply(&r);
+    let mut p = z.clone();
+    let mut rz = r.dot(&z);
+
+    while report.iterations() < max_iter && report.residual() > tol {
+        let ap = a.apply(&p);
+        let pap = p.dot(&ap);
+        if pap <= K::ZERO {
+            return Err(IterativeError::Breakdown {
+                iterations: report.iterations(),
+            });
+        }
+
+        let alpha = rz / pap;
+        axpy(&mut x, alpha, &p);
+        axpy(&mut r, -alpha, &ap);
+        report.push(r.norm() / b_norm);
+
+        z = m.apply(&r);
+        let rz_next = r.dot(&z);
+
+        // p = z + beta p
+        p.scl(rz_next / rz);
+        p.add(&z);
+        rz = rz_next;
+    }
+
+    finish(x, report, tol)
+}
+
+// Restarted GMRES(m) with modified Gram-Schmidt Arnoldi and Givens rotations.
+// Every inner step counts as one iteration.
+pub fn gmres<K, A>(a: &A, b: &Vector<K>, restart: usize) -> Solution<K>
+where
+    K: FloatBound,
+    A: LinearOperator<K> + ?Sized,
+{
+    gmres_with(
+        a,
+        b,
+        restart,
+        default_tolerance(),
+        default_max_iterations(b.len()),
+    )
+}
+
+pub fn gmres_with<K, A>(
+    a: &A,
+    b: &Vector<K>,
+    restart: usize,
+    tol: K,
+    max_iter: usize,
+) -> Solution<K>
+where
+    K: FloatBound,
+    A: LinearOperator<K> + ?Sized,
+{
+    gmres_preconditioned(a, b, &IdentityPreconditioner, restart, tol, max_iter)
+}
+
+// Right preconditioned: GMRES on A M^{-1} u = b, x = M^{-1} u, so the
+// reported residuals are those of the original system
+pub fn gmres_preconditioned<K, A, M>(
+    a: &A,
+    b: &Vector<K>,
+    m: &M,
+    restart: usize,
+    tol: K,
+    max_iter: usize,
+) -> Solution<K>
+where
+    K: FloatBound,
+    A: LinearOperator<K> + ?Sized,
+    M: Preconditioner<K> + ?Sized,
+{
+    check_system("gmres", a, b)?;
+
+    let n = b.len();
+    let size = restart.clamp(1, n.max(1));
+    let mut x = vector![K::ZERO; n];
+    let b_norm = b.norm();
+    let mut report = ConvergenceReport::new(K::ONE);
+    if b_norm.is_zero() {
+        report.residuals[0] = K::ZERO;
+        return Ok((x, report));
+    }
+
+    while report.iterations() < max_iter && report.residual() > tol {
+        let mut r = b.clone();
+        r.sub(&a.apply(&x));
+        let beta = r.norm();
+        if beta.is_zero() {
+            break;
+        }
+        r.scl(K::ONE / beta);
+
+        // h is column-major (size + 1) x size, g the rotated right hand side
+        let mut basis = vec![r];
+        let mut h = vec![vec![K::ZERO; size + 1]; size];
+        let mut cs = vec![K::ZERO; size];
+        let mut sn = vec![K::ZERO; size];
+        let mut g = vec![K::ZERO; size + 1];
+        g[0] = beta;
+
+        let mut k = 0;
+        while k < size && report.iterations() < max_iter {
+            let mut w = a.apply(&m.apply(&basis[k]));
+            for (i, v) in basis.iter().enumerate() {
+                h[k][i] = v.dot(&w);
+                axpy(&mut w, -h[k][i], v);
+            }
+            h[k][k + 1] = w.norm();
+
+            for i in 0..k {
+                let (hi, hj) = (h[k][i], h[k][i + 1]);
+                h[k][i] = cs[i] * hi + sn[i] * hj;
+                h[k][i + 1] = cs[i] * hj - sn[i] * hi;
+            }
+
+            let rho = (h[k][k] * h[k][k] + h[k][k + 1] * h[k][k + 1]).sqrt();
+            if rho.is_zero() {
+                return Err(IterativeError::Breakdown {
+                    iterations: report.iterations(),
+                });
+            }
+            cs[k] = h[k][k] / rho;
+            sn[k] = h[k][k + 1] / rho;
+            h[k][k] = rho;
+            g[k + 1] = -sn[k] * g[k];
+            g[k] = cs[k] * g[k];
+
+            report.push(g[k + 1].abs() / b_norm);
+            let lucky = h[k][k + 1].is_zero();
+            h[k][k + 1] = K::ZERO;
+            k += 1;
+
+            if lucky || report.residual() <= tol {
+                break;
+            }
+            w.scl(K::ONE / w.norm());
+            basis.push(w);
+        }
+
+        // Back substitution of the k x k triangular system, then x += M^{-1} V y
+        let mut y = vec![K::ZERO; k];
+        for i in (0..k).rev() {
+            let mut s = g[i];
+            for (j, y_j) in y.iter().enumerate().skip(i + 1) {
+                s -= &(h[j][i] * *y_j);
+            }
+            y[i] = s / h[i][i];
+        }
+        let mut update = vector![K::ZERO; n];
+        for (v, y_i) in basis.iter().zip(y) {
+            axpy(&mut update, y_i, v);
+        }
+        x.add(&m.apply(&update));
+
+        // The rotated estimate drifts from the true residual over restarts
+        let mut r = b.clone();
+        r.sub(&a.apply(&x));
+        let last = report.residuals.len() - 1;
+        report.residuals[last] = r.norm() / b_norm;
+    }
+
+    finish(x, report, tol)
+}
+
+// Stabilized bi-conjugate gradient, for general non-symmetric A
+pub fn bicgstab<K, A>(a: &A, b: &Vector<K>) -> Solution<K>
+where
+    K: FloatBound,
+    A: LinearOperator<K> + ?Sized,
+{
+    bicgstab_with(a, b, default_tolerance(), default_max_iterations(b.len()))
+}
+
+pub fn bicgstab_with<K, A>(a: &A, b: &Vector<K>, tol: K, max_iter: usize) -> Solution<K>
+where
+    K: FloatBound,
+    A: LinearOperator<K> + ?Sized,
+{
+    bicgstab_preconditioned(a, b, &IdentityPreconditioner, tol, max_iter)
+}
+
+// Right preconditioned, like gmres_preconditioned
+pub fn bicgstab_preconditioned<K, A, M>(
+    a: &A,
+    b: &Vector<K>,
+    m: &M,
+    tol: K,
+    max_iter: usize,
+) -> Solution<K>
+where
+    K: FloatBound,
+    A: LinearOperator<K> + ?Sized,
+    M: Preconditioner<K> + ?Sized,
+{
+    check_system("bicgstab", a, b)?;
+
+    let n = b.len();
+    let mut x = vector![K::ZERO; n];
+    let b_norm = b.norm();
+    let mut report = ConvergenceReport::new(K::ONE);
+    if b_norm.is_zero() {
+        report.residuals[0] = K::ZERO;
+        return Ok((x, report));
+    }
+
+    let mut r = b.clone();
+    let r_hat = r.clone();
+    let (mut rho, mut alpha, mut omega) = (K::ONE, K::ONE, K::ONE);
+    let mut p = vector![K::ZERO; n];
+    let mut v = vector![K::ZERO; n];
+
+    while report.iterations() < max_iter && report.residual() > tol {
+        let rho_next = r_hat.dot(&r);
+        if rho_next.is_zero() || omega.is_zero() {
+            return Err(IterativeError::Breakdown {
+                iterations: report.iterations(),
+            });
+        }
+
+        // p = r + beta (p - omega v)
+        let beta = (rho_next / rho) * (alpha / omega);
+        axpy(&mut p, -omega, &v);
+        p.scl(beta);
+        p.add(&r);
+
+        let p_hat = m.apply(&p);
+        v = a.apply(&p_hat);
+        let rv = r_hat.dot(&v);
+        if rv.is_zero() {
+            return Err(IterativeError::Breakdown {
+                iterations: report.iterations(),
+            });
+        }
+        alpha = rho_next / rv;
+        rho = rho_next;
+
+        // Half step: s = r - alpha v
+        let mut s = r;
+        axpy(&mut s, -alpha, &v);
+        axpy(&mut x, alpha, &p_hat);
+        let s_norm = s.norm();
+        if s_norm / b_norm <= tol {
+            report.push(s_norm / b_norm);
+            break;
+        }
+
+        let s_hat = m.apply(&s);
+        let t = a.apply(&s_hat);
+        let tt = t.dot(&t);
+        omega = if tt.is_zero() {
+            K::ZERO
+        } else {
+            t.dot(&s) / tt
+        };
+        axpy(&mut x, omega, &s_hat);
+
+        r = s;
+        axpy(&mut r, -omega, &t);
+        report.push(r.norm() / b_norm);
+    }
+
+    finish(x, report, tol)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::approx::assert_vector_approx_eq;
+    use crate::fixtures::poisson;
+    use crate::matrix::matrix;
+
+    // The same operator, applied as a stencil without storing anything
+    struct Stencil(usize);
+
+    impl LinearOperator<f64> for Stencil {
+        fn n_rows(&self) -> usize {
+            self.0
+        }
+
+        fn n_cols(&self) -> usize {
+            self.0
+        }
+
+        fn apply(&self, x: &Vector<f64>) -> Vector<f64> {
+            let n = self.0;
+            let mut y = vector![0.; n];
+            for i in 0..n {
+                y[i] = 2. * x[i];
+                if i > 0 {
+                    y[i] -= x[i - 1];
+                }
+                if i + 1 < n {
+                    y[i] -= x[i + 1];
+                }
+            }
+            y
+        }
+    }
+
+    fn non_symmetric() -> Matrix<f64> {
+        matrix![
+            [4., 1., 0., 0.],
+            [2., 5., 1., 0.],
+            [0., -1., 6., 2.],
+            [1., 0., 3., 7.]
+        ]
+    }
+
+    #[test]
+    fn cg_test() {
+        let n = 50;
+        let a = poisson(n);
+        let b = vector![1.; n];
+
+        let (x, report) = cg(&a, &b).unwrap();
+        let expected = a.to_dense().solve(&b).unwrap();
+        assert_vector_approx_eq!(x, expected, 1e-6);
+
+        // Exact arithmetic would need at most n steps
+        assert!(report.iterations() <= n);
+        assert_eq!(report.residuals().len(), report.iterations() + 1);
+        assert_eq!(report.residuals()[0], 1.);
+        assert!(report.residual() <= f64::EPSILON.sqrt());
+
+        let (y, _) = cg(&Stencil(n), &b).unwrap();
+        assert_vector_approx_eq!(y, x, 1e-9);
+    }
+
+    #[test]
+    fn gmres_test() {
+        let a = non_symmetric();
+        let b = vector![1., 2., 3., 4.];
+        let expected = a.solve(&b).unwrap();
+
+        // Full GMRES terminates within n steps
+        let (x, report) = gmres(&a, &b, 4).unwrap();
+        assert_vector_approx_eq!(x, expected, 1e-9);
+        assert!(report.iterations() <= 4);
+
+        // Restarted after every two inner steps
+        let (x, report) = gmres_with(&a, &b, 2, 1e-12, 100).unwrap();
+        assert_vector_approx_eq!(x, expected, 1e-9);
+        assert!(report.residual() <= 1e-12);
+
+        let (x, _) = gmres(&poisson(30), &vector![1.; 30], 30).unwrap();
+        assert_vector_approx_eq!(
+            x,
+            poisson(30).to_dense().solve(&vector![1.; 30]).unwrap(),
+            1e-6
+        );
+    }
+
+    #[test]
+    fn bicgstab_test() {
+        let a = non_symmetric();
+        let b = vector![1., 2., 3., 4.];
+
+        let (x, report) = bicgstab_with(&a, &b, 1e-12, 100).unwrap();
+        assert_vector_approx_eq!(x, a.solve(&b).unwrap(), 1e-9);
+        assert!(report.residual() <= 1e-12);
+
+        let (x, _) = bicgstab(&Stencil(40), &vector![1.; 40]).unwrap();
+        assert_vector_approx_eq!(
+            x,
+            poisson(40).to_dense().solve(&vector![1.; 40]).unwrap(),
+            1e-6
+        );
+    }
+
+    #[test]
+    fn report_test() {
+        let a = poisson(100);
+        let b = vector![1.; 100];
+
+        match cg_with(&a, &b, 1e-12, 3) {
+            Err(IterativeError::NoConvergence(report)) => {
+                assert_eq!(report.iterations(), 3);
+                assert_eq!(report.residuals().len(), 4);
+            }
+            other => panic!("expected NoConvergence, got {:?}", other),
+        }
+
+        // Zero right hand side converges immediately
+        let (x, report) = cg(&a, &vector![0.; 100]).unwrap();
+        assert_eq!(x, vector![0.; 100]);
+        assert_eq!(report.iterations(), 0);
+
+        assert!(matches!(
+            gmres(&a, &vector![1.; 3], 5),
+            Err(IterativeError::Matrix(MatrixError::Incompatible { .. }))
+        ));
+        assert!(matches!(
+            bicgstab(&matrix![[1., 2., 3.], [4., 5., 6.]], &vector![1., 2.]),
+            Err(IterativeError::Matrix(MatrixError::NotSquare { .. }))
+        ));
+
+        // Indefinite matrices are detected by CG
+        assert_eq!(
+            cg(&matrix![[1., 0.], [0., -1.]], &vector![1., 1.]),
+            Err(IterativeError::Breakdown { iterations: 0 })
+        );
+    }
+}
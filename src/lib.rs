@@ -1,6 +1,5 @@
 use std::error::Error;
 use std::fmt;
-use std::fmt::Write;
 use std::ops;
 
 use num_integer::Roots;
@@ -28,9 +27,24 @@ pub trait FieldBound:
     + for <'a> ops::DivAssign<&'a Self>
 {
     const ZERO: Self;
+    // Containers such as Vector have no multiplicative identity and leave it
+    // unset, so using it there fails to compile
+    const ONE: Self = panic!("no multiplicative identity");
+    // Set when `/` truncates, so elimination has to stay fraction-free
+    const INTEGRAL: bool = false;
     fn abs(&self) -> Self;
     fn sqrt(&self) -> Self;
     fn is_zero(&self) -> bool;
+
+    // Relative rounding error of one operation, zero for exact fields
+    fn epsilon() -> Self {
+        Self::ZERO
+    }
+
+    // Identity for real fields
+    fn conjugate(&self) -> Self {
+        self.clone()
+    }
 }
 
 macro_rules! impl_fbound_required {
@@ -38,6 +52,7 @@ macro_rules! impl_fbound_required {
         $(
             impl FieldBound for $t {
                 const ZERO: Self = 0.0;
+                const ONE: Self = 1.0;
 
                 fn abs(&self) -> Self {
                     Signed::abs(self)
@@ -47,8 +62,13 @@ macro_rules! impl_fbound_required {
                     Float::sqrt(self.clone())
                 }
 
+                // Exact test, rounding is handled through explicit tolerances
                 fn is_zero(&self) -> bool {
-                    Signed::abs(self) <= 0e-10
+                    *self == Self::ZERO
+                }
+
+                fn epsilon() -> Self {
+                    Self::EPSILON
                 }
             }
         )+
@@ -58,6 +78,8 @@ macro_rules! impl_fbound_required {
         $(
             impl FieldBound for $t {
                 const ZERO: Self = 0;
+                const ONE: Self = 1;
+                const INTEGRAL: bool = true;
 
                 fn abs(&self) -> Self {
                     Signed::abs(self)
@@ -78,18 +100,56 @@ macro_rules! impl_fbound_required {
 impl_fbound_required!(f32 f64, float);
 impl_fbound_required!(i8 i16 i32 i64 i128 isize, integer);
 
-#[derive(Debug, PartialEq)]
+// Scalars for the numerical (approximate) algorithms
+pub trait FloatBound: FieldBound + Copy {
+    const EPSILON: Self;
+    fn from_f64(v: f64) -> Self;
+}
+
+macro_rules! impl_float_bound {
+    ($($t:ident) +) => {
+        $(
+            impl FloatBound for $t {
+                const EPSILON: Self = $t::EPSILON;
+
+                fn from_f64(v: f64) -> Self {
+                    v as $t
+                }
+            }
+        )+
+    };
+}
+
+impl_float_bound!(f32 f64);
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct D1 {
     length: usize,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct D2 {
     rows: usize,
     cols: usize,
 }
 
-#[derive(Debug, PartialEq)]
+impl D1 {
+    pub fn length(&self) -> usize {
+        self.length
+    }
+}
+
+impl D2 {
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum Dimension {
     D1(D1),
     D2(D2),
@@ -118,33 +178,145 @@ impl Dimension {
             Dimension::D2(d2_self) => match other {
                 Dimension::D1(_) => false,
                 Dimension::D2(d2_other) => {
-                    if d2_self.rows == d2_other.cols && d2_self.cols == d2_other.rows {
-                        true
-                    } else {
-                        false
-                    }
+                    d2_self.rows == d2_other.cols && d2_self.cols == d2_other.rows
                 }
             },
         }
     }
 }
 
-#[derive(Debug)]
-pub struct IncompatibleError {
-    message: String,
+impl fmt::Display for Dimension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Dimension::D1(d) => write!(f, "{}", d.length),
+            Dimension::D2(d) => write!(f, "{}x{}", d.rows, d.cols),
+        }
+    }
+}
+
+// Returned by the try_* operations; the panicking variants print the same message
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatrixError {
+    Incompatible {
+        operation: &'static str,
+        expected: Dimension,
+        actual: Dimension,
+    },
+    NotSquare {
+        operation: &'static str,
+        shape: Dimension,
+    },
+    Empty {
+        operation: &'static str,
+    },
+    NotRectangular {
+        operation: &'static str,
+    },
+    NotInvertible,
+    // No unique solution. Factorizations stop here; Matrix::solve goes on to
+    // separate this from Inconsistent.
+    Singular,
+    Inconsistent, // no solution exists
+    NotSymmetric,
+    NotPositiveDefinite {
+        order: usize, // first failing leading minor
+    },
+    ZeroPivot {
+        order: usize,
+    },
+    NoConvergence {
+        iterations: usize,
+    },
+    // A real eigenvalue <= 0, so there is no real principal root or logarithm
+    NoRealSquareRoot,
+    NoRealLogarithm,
+    // Needs exact division, which the integer types do not have
+    NotAField {
+        operation: &'static str,
+    },
+    // An entry does not fit in the integer type
+    Overflow {
+        operation: &'static str,
+    },
 }
 
-impl Error for IncompatibleError {}
+impl Error for MatrixError {}
 
-impl fmt::Display for IncompatibleError {
+impl fmt::Display for MatrixError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.message)
+        match self {
+            MatrixError::Incompatible {
+                operation,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{}: received incompatible object of shape {}, expected {}",
+                operation, actual, expected
+            ),
+            MatrixError::NotSquare { operation, shape } => write!(
+                f,
+                "{}: expected a square matrix, received shape {}",
+                operation, shape
+            ),
+            MatrixError::Empty { operation } => write!(f, "{}: object is empty", operation),
+            MatrixError::NotRectangular { operation } => {
+                write!(f, "{}: rows have different lengths", operation)
+            }
+            MatrixError::NotInvertible => write!(f, "inverse is not found"),
+            MatrixError::Singular => write!(f, "system matrix is singular"),
+            MatrixError::Inconsistent => write!(f, "system is inconsistent"),
+            MatrixError::NotSymmetric => write!(f, "matrix is not symmetric"),
+            MatrixError::NotPositiveDefinite { order } => write!(
+                f,
+                "matrix is not positive definite: leading minor of order {} is not positive",
+                order
+            ),
+            MatrixError::ZeroPivot { order } => write!(
+                f,
+                "matrix needs pivoting: leading minor of order {} is singular",
+                order
+            ),
+            MatrixError::NoConvergence { iterations } => {
+                write!(f, "no convergence after {} iterations", iterations)
+            }
+            MatrixError::NoRealSquareRoot => write!(
+                f,
+                "matrix has a non-positive real eigenvalue: no real principal square root"
+            ),
+            MatrixError::NoRealLogarithm => write!(
+                f,
+                "matrix has a non-positive real eigenvalue: no real principal logarithm"
+            ),
+            MatrixError::NotAField { operation } => {
+                write!(f, "{}: not available for integer types", operation)
+            }
+            MatrixError::Overflow { operation } => {
+                write!(f, "{}: integer overflow", operation)
+            }
+        }
     }
 }
 
-impl IncompatibleError {
-    fn new(message: String) -> Self {
-        IncompatibleError { message }
+impl MatrixError {
+    pub(crate) fn incompatible(
+        operation: &'static str,
+        expected: Dimension,
+        actual: Dimension,
+    ) -> Self {
+        MatrixError::Incompatible {
+            operation,
+            expected,
+            actual,
+        }
+    }
+
+    pub(crate) fn size(operation: &'static str, expected: usize, actual: usize) -> Self {
+        MatrixError::incompatible(
+            operation,
+            Dimension::D1(D1 { length: expected }),
+            Dimension::D1(D1 { length: actual }),
+        )
     }
 }
 
@@ -173,35 +345,23 @@ pub trait VectorSpace {
 
     // Provided functions
 
-    fn eq_shape_compatible(&self, v: &Self) -> Result<Dimension, IncompatibleError> {
+    fn eq_shape_compatible(&self, v: &Self) -> Result<Dimension, MatrixError> {
         if self.shape() == v.shape() {
             Ok(self.shape())
         } else {
-            let mut msg = String::new();
-            write!(
-                &mut msg,
-                "received incompatible object of shape {:?}, expected {:?}",
+            Err(MatrixError::incompatible(
+                "shape check",
+                self.shape(),
                 v.shape(),
-                self.shape()
-            )
-            .unwrap();
-            Err(IncompatibleError::new(msg))
+            ))
         }
     }
 
-    fn eq_size_compatible(&self, v: &Self) -> Result<usize, IncompatibleError> {
+    fn eq_size_compatible(&self, v: &Self) -> Result<usize, MatrixError> {
         if self.size() == v.size() {
             Ok(self.size())
         } else {
-            let mut msg = String::new();
-            write!(
-                &mut msg,
-                "received incompatible object of size {:?}, expected {:?}",
-                v.size(),
-                self.size()
-            )
-            .unwrap();
-            Err(IncompatibleError::new(msg))
+            Err(MatrixError::size("size check", self.size(), v.size()))
         }
     }
 
@@ -222,10 +382,46 @@ pub trait VectorSpace {
             }
         }
     }
+
+    fn try_add(&mut self, v: &Self) -> Result<(), MatrixError> {
+        if self.shape() != v.shape() {
+            return Err(MatrixError::incompatible("add", self.shape(), v.shape()));
+        }
+        self.add(v);
+        Ok(())
+    }
+
+    fn try_sub(&mut self, v: &Self) -> Result<(), MatrixError> {
+        if self.shape() != v.shape() {
+            return Err(MatrixError::incompatible("sub", self.shape(), v.shape()));
+        }
+        self.sub(v);
+        Ok(())
+    }
 }
 
+pub mod approx;
+pub mod bitmatrix;
+pub mod cholesky;
+pub mod complex;
+pub mod eigen;
+pub mod fixed;
+pub mod iterative;
+pub mod lu;
+pub mod matfun;
 pub mod matrix;
+pub mod modular;
+pub mod preconditioner;
+pub mod qr;
+pub mod rational;
+pub mod sparse;
+pub mod stationary;
+pub mod svd;
 pub mod vector;
+pub mod view;
+
+#[cfg(test)]
+mod fixtures;
 
 #[cfg(test)]
 mod tests {
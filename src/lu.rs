@@ -0,0 +1,377 @@
+use super::FieldBound;
+use super::MatrixError;
+
+use super::matrix::matrix;
+use super::matrix::Matrix;
+use super::matrix::Tolerance;
+use super::vector::vector;
+use super::vector::Vector;
+
+// Factorization PA = LU with partial pivoting.
+// L (unit diagonal, implicit) and U are stored in a single matrix.
+// Needs exact division, so integer types are refused.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LU<K: FieldBound> {
+    lu: Matrix<K>,
+    perm: Vec<usize>, // row i of PA is row perm[i] of A
+    sign: K,
+    tol: K, // pivots with |u_kk| <= tol count as zero
+}
+
+impl<K: FieldBound> Matrix<K> {
+    pub fn lu(&self) -> LU<K> {
+        match self.try_lu() {
+            Ok(lu) => lu,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    pub fn try_lu(&self) -> Result<LU<K>, MatrixError> {
+        self.try_lu_with_tol(self.default_tolerance())
+    }
+
+    pub fn lu_with_tol(&self, tol: impl Into<Tolerance<K>>) -> LU<K> {
+        match self.try_lu_with_tol(tol) {
+            Ok(lu) => lu,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    pub fn try_lu_with_tol(&self, tol: impl Into<Tolerance<K>>) -> Result<LU<K>, MatrixError> {
+        self.check_square("lu")?;
+        if K::INTEGRAL {
+            return Err(MatrixError::NotAField { operation: "lu" });
+        }
+        let tol = tol.into().threshold(self);
+
+        let n = self.n_rows();
+        let mut lu = self.clone();
+        let mut perm: Vec<usize> = (0..n).collect();
+        let mut sign = K::ONE;
+
+        for k in 0..n {
+            let mut p = k;
+            for i in (k + 1)..n {
+                if lu[(i, k)].abs() > lu[(p, k)].abs() {
+                    p = i;
+                }
+            }
+
+            if p != k {
+                lu.swap_rows(p, k);
+                perm.swap(p, k);
+                sign = -sign;
+            }
+
+            // Pivots at or below tol only make the factorization singular,
+            // they are still eliminated so that PA = LU holds
+            if lu[(k, k)].is_zero() {
+                continue;
+            }
+
+            for i in (k + 1)..n {
+                let mut f = lu[(i, k)].clone();
+                f /= &lu[(k, k)];
+
+                for j in (k + 1)..n {
+                    let d = f.clone() * lu[(k, j)].clone();
+                    lu[(i, j)] -= &d;
+                }
+                lu[(i, k)] = f;
+            }
+        }
+
+        Ok(LU {
+            lu,
+            perm,
+            sign,
+            tol,
+        })
+    }
+}
+
+impl<K: FieldBound> Matrix<K> {
+    // Number of pivots when forward elimination with partial pivoting, as
+    // in lu(), runs over a rectangular matrix and skips columns without a
+    // pivot above tol. Integer fields take fraction-free (Bareiss) steps.
+    pub(crate) fn elimination_rank(&self, tol: K) -> usize {
+        let (m, n) = (self.n_rows(), self.n_cols());
+        let mut a = self.clone();
+        let mut prev = K::ONE;
+        let mut rank = 0;
+
+        for k in 0..n {
+            if rank == m {
+                break;
+            }
+
+            let mut p = rank;
+            for i in (rank + 1)..m {
+                if a[(i, k)].abs() > a[(p, k)].abs() {
+                    p = i;
+                }
+            }
+            if a[(p, k)].abs() <= tol {
+                continue;
+            }
+            a.swap_rows(p, rank);
+
+            let pivot = a[(rank, k)].clone();
+            for i in (rank + 1)..m {
+                let f = a[(i, k)].clone();
+                for j in (k + 1)..n {
+                    let d = f.clone() * a[(rank, j)].clone();
+                    if K::INTEGRAL {
+                        let mut v = a[(i, j)].clone() * pivot.clone() - d;
+                        v /= &prev;
+                        a[(i, j)] = v;
+                    } else {
+                        let mut d = d;
+                        d /= &pivot;
+                        a[(i, j)] -= &d;
+                    }
+                }
+                a[(i, k)] = K::ZERO;
+            }
+
+            prev = pivot;
+            rank += 1;
+        }
+
+        rank
+    }
+}
+
+impl<K: FieldBound> LU<K> {
+    fn n(&self) -> usize {
+        self.perm.len()
+    }
+
+    pub fn l(&self) -> Matrix<K> {
+        let n = self.n();
+        let mut l = matrix![K::ZERO; n];
+
+        for i in 0..n {
+            for j in 0..i {
+                l[(i, j)] = self.lu[(i, j)].clone();
+            }
+            l[(i, i)] = K::ONE;
+        }
+
+        l
+    }
+
+    pub fn u(&self) -> Matrix<K> {
+        let n = self.n();
+        let mut u = matrix![K::ZERO; n];
+
+        for i in 0..n {
+            for j in i..n {
+                u[(i, j)] = self.lu[(i, j)].clone();
+            }
+        }
+
+        u
+    }
+
+    pub fn p(&self) -> Matrix<K> {
+        let n = self.n();
+        let mut p = matrix![K::ZERO; n];
+
+        for (i, &r) in self.perm.iter().enumerate() {
+            p[(i, r)] = K::ONE;
+        }
+
+        p
+    }
+
+    pub fn permutation(&self) -> &[usize] {
+        &self.perm
+    }
+
+    pub fn pivot_sign(&self) -> K {
+        self.sign.clone()
+    }
+
+    pub fn is_singular(&self) -> bool {
+        (0..self.n()).any(|i| self.lu[(i, i)].abs() <= self.tol)
+    }
+
+    pub fn determinant(&self) -> K {
+        let mut det = self.sign.clone();
+
+        for i in 0..self.n() {
+            det *= &self.lu[(i, i)];
+        }
+
+        det
+    }
+
+    // Any singular U gives Singular without checking b; Matrix::solve tells
+    // consistent and inconsistent systems apart
+    pub fn solve(&self, b: &Vector<K>) -> Result<Vector<K>, MatrixError> {
+        let n = self.n();
+
+        if b.len() != n {
+            return Err(MatrixError::size("solve", n, b.len()));
+        }
+
+        if self.is_singular() {
+            return Err(MatrixError::Singular);
+        }
+
+        // Forward substitution: Ly = Pb
+        let mut x = vector![K::ZERO; n];
+        for i in 0..n {
+            let mut acc = b[self.perm[i]].clone();
+            for j in 0..i {
+                let d = self.lu[(i, j)].clone() * x[j].clone();
+                acc -= &d;
+            }
+            x[i] = acc;
+        }
+
+        // Backward substitution: Ux = y
+        for i in (0..n).rev() {
+            let mut acc = x[i].clone();
+            for j in (i + 1)..n {
+                let d = self.lu[(i, j)].clone() * x[j].clone();
+                acc -= &d;
+            }
+            acc /= &self.lu[(i, i)];
+            x[i] = acc;
+        }
+
+        Ok(x)
+    }
+
+    pub fn solve_many(&self, b: &Matrix<K>) -> Result<Matrix<K>, MatrixError> {
+        let mut x = b.clone();
+
+        for k in 0..b.n_cols() {
+            let mut col = vector![K::ZERO; b.n_rows()];
+            for i in 0..b.n_rows() {
+                col[i] = b[(i, k)].clone();
+            }
+
+            let sol = self.solve(&col)?;
+            for i in 0..b.n_rows() {
+                x[(i, k)] = sol[i].clone();
+            }
+        }
+
+        Ok(x)
+    }
+
+    pub fn inverse(&self) -> Result<Matrix<K>, MatrixError> {
+        let n = self.n();
+        let mut inv = matrix![K::ZERO; n];
+        let mut e = vector![K::ZERO; n];
+
+        for j in 0..n {
+            e[j] = K::ONE;
+            let col = self.solve(&e).map_err(|_| MatrixError::NotInvertible)?;
+            for i in 0..n {
+                inv[(i, j)] = col[i].clone();
+            }
+            e[j] = K::ZERO;
+        }
+
+        Ok(inv)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::approx::assert_matrix_approx_eq;
+
+    #[test]
+    fn lu_test() {
+        let m = matrix![[2., 1., 1.], [4., -6., 0.], [-2., 7., 2.]];
+        let lu = m.lu();
+
+        assert_eq!(lu.permutation(), &[1, 0, 2]);
+        assert_eq!(lu.pivot_sign(), -1.);
+        assert_eq!(lu.l(), matrix![[1., 0., 0.], [0.5, 1., 0.], [-0.5, 1., 1.]]);
+        assert_eq!(lu.u(), matrix![[4., -6., 0.], [0., 4., 1.], [0., 0., 1.]]);
+        assert_eq!(lu.p().mul_mat(&m), lu.l().mul_mat(&lu.u()));
+    }
+
+    #[test]
+    fn lu_singular_test() {
+        let m = matrix![[1., 2., 3.], [2., 4., 6.], [1., 0., 1.]];
+        let lu = m.lu();
+
+        assert!(lu.is_singular());
+        assert_eq!(lu.determinant(), 0.);
+        assert!(matches!(
+            lu.solve(&vector![1., 2., 3.]),
+            Err(MatrixError::Singular)
+        ));
+        assert!(lu.inverse().is_err());
+    }
+
+    #[test]
+    fn lu_tiny_pivot_test() {
+        // The second pivot, 2e-12, is nonzero but below the tolerance
+        let m = matrix![[1., 1., 1.], [1., 1. + 1e-12, 2.], [1., 1. + 2e-12, 5.]];
+        let lu = m.lu_with_tol(1e-10);
+
+        assert!(lu.is_singular());
+        assert_matrix_approx_eq!(lu.p().mul_mat(&m), lu.l().mul_mat(&lu.u()), 1e-12);
+        assert!(lu.l().as_slice().iter().all(|x| x.abs() <= 1.));
+    }
+
+    #[test]
+    fn lu_integer_test() {
+        assert_eq!(
+            matrix![[2, 1], [1, 1]].try_lu(),
+            Err(MatrixError::NotAField { operation: "lu" })
+        );
+    }
+
+    #[test]
+    fn lu_determinant_test() {
+        let m = matrix![[2., 1., 1.], [4., -6., 0.], [-2., 7., 2.]];
+
+        assert_eq!(m.lu().determinant(), -16.);
+        assert_eq!(matrix![[0., 1.], [1., 0.]].lu().determinant(), -1.);
+    }
+
+    #[test]
+    fn lu_solve_test() {
+        let m = matrix![[2., 1., 1.], [4., -6., 0.], [-2., 7., 2.]];
+        let lu = m.lu();
+
+        assert_eq!(
+            lu.solve(&vector![5., -2., 9.]).unwrap(),
+            vector![1., 1., 2.]
+        );
+        assert!(matches!(
+            lu.solve(&vector![1., 2.]),
+            Err(MatrixError::Incompatible { .. })
+        ));
+    }
+
+    #[test]
+    fn lu_solve_many_test() {
+        let m = matrix![[2., 1., 1.], [4., -6., 0.], [-2., 7., 2.]];
+        let b = matrix![[5., 2.], [-2., 4.], [9., -2.]];
+
+        assert_eq!(
+            m.lu().solve_many(&b).unwrap(),
+            matrix![[1., 1.], [1., 0.], [2., 0.]]
+        );
+    }
+
+    #[test]
+    fn lu_inverse_test() {
+        let m = matrix![[4., 2.], [2., 2.]];
+        let inv = m.lu().inverse().unwrap();
+
+        assert_eq!(inv, matrix![[0.5, -0.5], [-0.5, 1.]]);
+        assert!(m.mul_mat(&inv).is_identity());
+    }
+}
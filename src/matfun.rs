@@ -0,0 +1,421 @@
+use super::FloatBound;
+use super::MatrixError;
+use super::VectorSpace;
+
+use super::matrix::Matrix;
+
+// Denman-Beavers iterations per square root
+const MAX_SQRT_ITERATIONS: usize = 100;
+// Square roots taken by logm before the series is accurate
+const MAX_LOG_SQUARE_ROOTS: usize = 64;
+// Terms of the atanh series in logm
+const MAX_ATANH_TERMS: usize = 50;
+
+// Degree m Padé approximants of exp, with the largest 1-norm for which each is
+// accurate to double precision (Higham, 2005). Coefficients b_0, ..., b_m.
+const PADE: [(f64, &[f64]); 5] = [
+    (1.495585217958292e-2, &[120., 60., 12., 1.]),
+    (2.53939833006323e-1, &[30240., 15120., 3360., 420., 30., 1.]),
+    (
+        9.504178996162932e-1,
+        &[
+            17297280., 8648640., 1995840., 277200., 25200., 1512., 56., 1.,
+        ],
+    ),
+    (
+        2.097847961257068,
+        &[
+            17643225600.,
+            8821612800.,
+            2075673600.,
+            302702400.,
+            30270240.,
+            2162160.,
+            110880.,
+            3960.,
+            90.,
+            1.,
+        ],
+    ),
+    (
+        5.371920351148152,
+        &[
+            64764752532480000.,
+            32382376266240000.,
+            7771770303897600.,
+            1187353796428800.,
+            129060195264000.,
+            10559470521600.,
+            670442572800.,
+            33522128640.,
+            1323241920.,
+            40840800.,
+            960960.,
+            16380.,
+            182.,
+            1.,
+        ],
+    ),
+];
+
+impl<K: FloatBound> Matrix<K> {
+    // Induced 1-norm, the largest absolute column sum
+    fn norm_1_induced(&self) -> K {
+        let mut max = K::ZERO;
+        for j in 0..self.n_cols() {
+            let mut sum = K::ZERO;
+            for x in self.col(j) {
+                sum += &x.abs();
+            }
+            if sum > max {
+                max = sum;
+            }
+        }
+        max
+    }
+
+    // r(A) = (V - U)^{-1} (V + U), U and V the odd and even parts of the
+    // numerator polynomial
+    fn pade(&self, b: &[f64]) -> Result<Matrix<K>, MatrixError> {
+        let a2 = self.mul_mat(self);
+        let mut power = self.identity();
+        let mut u = matrix_zeros(self.n_rows());
+        let mut v = matrix_zeros(self.n_rows());
+
+        for (j, pair) in b.chunks(2).enumerate() {
+            let mut term = power.clone();
+            term.scl(K::from_f64(pair[0]));
+            v.add(&term);
+
+            let mut term = power.clone();
+            term.scl(K::from_f64(pair[1]));
+            u.add(&term);
+
+            if j + 1 < b.len() / 2 {
+                power = power.mul_mat(&a2);
+            }
+        }
+        let u = self.mul_mat(&u);
+
+        let mut denominator = v.clone();
+        denominator.sub(&u);
+        let mut numerator = v;
+        numerator.add(&u);
+
+        denominator.solve_many(&numerator)
+    }
+
+    // Scaling and squaring: exp(A) = r(A / 2^s)^(2^s)
+    pub fn expm(&self) -> Matrix<K> {
+        match self.try_expm() {
+            Ok(m) => m,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    pub fn try_expm(&self) -> Result<Matrix<K>, MatrixError> {
+        self.check_square("expm")?;
+
+        let norm = self.norm_1_induced();
+        for (theta, b) in &PADE[..4] {
+            if norm <= K::from_f64(*theta) {
+                return self.pade(b);
+            }
+        }
+
+        let (theta, b) = PADE[4];
+        let mut s = 0;
+        let mut scale = K::ONE;
+        while norm * scale > K::from_f64(theta) {
+            s += 1;
+            scale /= &K::from_f64(2.);
+        }
+
+        let mut scaled = self.clone();
+        scaled.scl(scale);
+        let mut r = scaled.pade(b)?;
+        for _ in 0..s {
+            r = r.mul_mat(&r);
+        }
+        Ok(r)
+    }
+
+    // Real eigenvalues <= tol rule out a real principal square root or logarithm
+    fn has_non_positive_eigenvalue(&self) -> Result<bool, MatrixError> {
+        let tol = self.default_tolerance();
+        let values = self.eigenvalues()?;
+        Ok(values.iter().any(|(re, im)| im.is_zero() && *re <= tol))
+    }
+
+    // Principal square root, the one whose eigenvalues have positive real part
+    pub fn sqrtm(&self) -> Matrix<K> {
+        match self.try_sqrtm() {
+            Ok(m) => m,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    pub fn try_sqrtm(&self) -> Result<Matrix<K>, MatrixError> {
+        self.check_square("sqrtm")?;
+        if self.has_non_positive_eigenvalue()? {
+            return Err(MatrixError::NoRealSquareRoot);
+        }
+        self.denman_beavers()
+    }
+
+    // Y <- (Y + Z^{-1}) / 2, Z <- (Z + Y^{-1}) / 2 from Y = A, Z = I converges
+    // quadratically to Y = A^{1/2}, Z = A^{-1/2}
+    fn denman_beavers(&self) -> Result<Matrix<K>, MatrixError> {
+        let half = K::from_f64(0.5);
+        let mut y = self.clone();
+        let mut z = self.identity();
+        let mut settled = false;
+
+        for _ in 0..MAX_SQRT_ITERATIONS {
+            let (y_inv, z_inv) = match (y.inverse(), z.inverse()) {
+                (Ok(y_inv), Ok(z_inv)) => (y_inv, z_inv),
+                _ => return Err(MatrixError::NoRealSquareRoot),
+            };
+
+            let mut y_next = y.clone();
+            y_next.add(&z_inv);
+            y_next.scl(half);
+            z.add(&y_inv);
+            z.scl(half);
+
+            let mut delta = y_next.clone();
+            delta.sub(&y);
+            let change = delta.norm() / y_next.norm();
+            y = y_next;
+
+            // Quadratic convergence: one more step after reaching sqrt(eps)
+            // brings the error down to rounding level
+            if settled {
+                return Ok(y);
+            }
+            settled = change <= K::EPSILON.sqrt();
+        }
+
+        Err(MatrixError::NoConvergence {
+            iterations: MAX_SQRT_ITERATIONS,
+        })
+    }
+
+    // Principal logarithm by inverse scaling and squaring: take square roots
+    // until A^(1/2^k) is close to I, then log(A) = 2^k log(A^(1/2^k))
+    pub fn logm(&self) -> Matrix<K> {
+        match self.try_logm() {
+            Ok(m) => m,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    pub fn try_logm(&self) -> Result<Matrix<K>, MatrixError> {
+        self.check_square("logm")?;
+        if self.has_non_positive_eigenvalue()? {
+            return Err(MatrixError::NoRealLogarithm);
+        }
+
+        let id = self.identity();
+        let mut a = self.clone();
+        let mut k = 0;
+        loop {
+            let mut d = a.clone();
+            d.sub(&id);
+            if d.norm_1_induced() <= K::from_f64(0.25) {
+                break;
+            }
+            if k == MAX_LOG_SQUARE_ROOTS {
+                return Err(MatrixError::NoConvergence { iterations: k });
+            }
+            a = a.denman_beavers()?;
+            k += 1;
+        }
+
+        // log(A) = 2 atanh(Z), where Z = (A + I)^{-1} (A - I) has norm below 1/7
+        let mut numerator = a.clone();
+        numerator.sub(&id);
+        let mut denominator = a;
+        denominator.add(&id);
+        let z = match denominator.solve_many(&numerator) {
+            Ok(z) => z,
+            Err(MatrixError::Singular | MatrixError::Inconsistent) => {
+                return Err(MatrixError::NoRealLogarithm)
+            }
+            Err(e) => return Err(e),
+        };
+
+        let mut sum = z.atanh_series()?;
+        let mut scale = K::from_f64(2.);
+        for _ in 0..k {
+            scale *= &K::from_f64(2.);
+        }
+        sum.scl(scale);
+        Ok(sum)
+    }
+
+    // atanh(Z) = Z + Z^3 / 3 + Z^5 / 5 + ..., summed until the terms fall
+    // below rounding level
+    fn atanh_series(self) -> Result<Matrix<K>, MatrixError> {
+        let z2 = self.mul_mat(&self);
+        let mut power = self.clone();
+        let mut sum = self;
+        for j in 1..=MAX_ATANH_TERMS {
+            power = power.mul_mat(&z2);
+            let mut term = power.clone();
+            term.scl(K::ONE / K::from_f64((2 * j + 1) as f64));
+            sum.add(&term);
+            if term.norm() <= K::EPSILON * sum.norm() {
+                return Ok(sum);
+            }
+        }
+
+        Err(MatrixError::NoConvergence {
+            iterations: MAX_ATANH_TERMS,
+        })
+    }
+}
+
+fn matrix_zeros<K: FloatBound>(n: usize) -> Matrix<K> {
+    Matrix::from_col_major(n, n, vec![K::ZERO; n * n])
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::approx::assert_matrix_approx_eq;
+    use crate::matrix::matrix;
+
+    #[test]
+    fn expm_test() {
+        let zero = matrix![[0., 0.], [0., 0.]];
+        assert_eq!(zero.expm(), zero.identity());
+
+        let d = matrix![[1., 0.], [0., -2.]];
+        assert_matrix_approx_eq!(
+            d.expm(),
+            matrix![[1f64.exp(), 0.], [0., (-2f64).exp()]],
+            1e-14
+        );
+
+        // Nilpotent: exp(N) = I + N
+        assert_eq!(
+            matrix![[0., 1.], [0., 0.]].expm(),
+            matrix![[1., 1.], [0., 1.]]
+        );
+
+        let t = 0.7f64;
+        assert_matrix_approx_eq!(
+            matrix![[0., t], [-t, 0.]].expm(),
+            matrix![[t.cos(), t.sin()], [-t.sin(), t.cos()]],
+            1e-14
+        );
+        assert_matrix_approx_eq!(
+            matrix![[0f32, 1.], [-1., 0.]].expm(),
+            matrix![[1f32.cos(), 1f32.sin()], [-1f32.sin(), 1f32.cos()]],
+            1e-6
+        );
+    }
+
+    #[test]
+    fn expm_scaling_test() {
+        // Moler and Van Loan's example, eigenvalues -1 and -17
+        let a = matrix![[-49., 24.], [-64., 31.]];
+        let (e1, e17) = ((-1f64).exp(), (-17f64).exp());
+        let expected = matrix![
+            [-2. * e1 + 3. * e17, 1.5 * e1 - 1.5 * e17],
+            [-4. * e1 + 4. * e17, 3. * e1 - 2. * e17]
+        ];
+        assert_matrix_approx_eq!(a.expm(), expected, 1e-12);
+
+        assert!(matches!(
+            matrix![[1., 2., 3.], [4., 5., 6.]].try_expm(),
+            Err(MatrixError::NotSquare { .. })
+        ));
+    }
+
+    #[test]
+    fn sqrtm_test() {
+        let a = matrix![[33., 24.], [48., 57.]];
+        assert_matrix_approx_eq!(a.sqrtm(), matrix![[5., 2.], [4., 7.]], 1e-12);
+
+        let spd = matrix![[4., 1., 0.], [1., 3., 1.], [0., 1., 2.]];
+        let root = spd.sqrtm();
+        assert_matrix_approx_eq!(root.mul_mat(&root), spd, 1e-12);
+
+        // Complex eigenvalues 1 +- 2i still have a real principal root
+        let r = matrix![[1., -2.], [2., 1.]];
+        let root = r.sqrtm();
+        assert_matrix_approx_eq!(root.mul_mat(&root), r, 1e-12);
+
+        assert_eq!(
+            matrix![[-1., 0.], [0., 4.]].try_sqrtm(),
+            Err(MatrixError::NoRealSquareRoot)
+        );
+        assert_eq!(
+            matrix![[1., 1.], [1., 1.]].try_sqrtm(),
+            Err(MatrixError::NoRealSquareRoot)
+        );
+    }
+
+    #[test]
+    fn logm_test() {
+        let id = matrix![[1., 0.], [0., 1.]];
+        assert_eq!(id.logm(), matrix![[0., 0.], [0., 0.]]);
+
+        let b = matrix![[0.1, 0.2, 0.], [0., 0.3, -0.4], [0.5, 0., -0.2]];
+        assert_matrix_approx_eq!(b.expm().logm(), b, 1e-12);
+
+        // Large norm needs several square roots first
+        let d = matrix![[1000., 0.], [0., 0.001]];
+        assert_matrix_approx_eq!(
+            d.logm(),
+            matrix![[1000f64.ln(), 0.], [0., 0.001f64.ln()]],
+            1e-10
+        );
+
+        let t = 2.5f64;
+        let rotation = matrix![[t.cos(), t.sin()], [-t.sin(), t.cos()]];
+        assert_matrix_approx_eq!(rotation.logm(), matrix![[0., t], [-t, 0.]], 1e-12);
+
+        assert_eq!(
+            matrix![[-2., 0.], [0., 1.]].try_logm(),
+            Err(MatrixError::NoRealLogarithm)
+        );
+        assert_eq!(
+            matrix![[0., 1.], [0., 0.]].try_logm(),
+            Err(MatrixError::NoRealLogarithm)
+        );
+        assert!(matches!(
+            matrix![[1., 2.]].try_logm(),
+            Err(MatrixError::NotSquare { .. })
+        ));
+        assert!(matches!(
+            matrix![[1., 2.]].try_sqrtm(),
+            Err(MatrixError::NotSquare { .. })
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "no real principal logarithm")]
+    fn logm_panic_test() {
+        matrix![[-2., 0.], [0., 1.]].logm();
+    }
+
+    #[test]
+    fn atanh_series_test() {
+        // Near the edge of the radius of convergence 50 terms are not enough
+        assert_eq!(
+            matrix![[0.99]].atanh_series(),
+            Err(MatrixError::NoConvergence {
+                iterations: MAX_ATANH_TERMS
+            })
+        );
+        assert_matrix_approx_eq!(
+            matrix![[0.1]].atanh_series().unwrap(),
+            matrix![[0.1f64.atanh()]],
+            1e-15
+        );
+    }
+}
@@ -1,46 +1,81 @@
 use super::Dimension;
 use super::FieldBound;
+use super::MatrixError;
 use super::VectorSpace;
+use super::D1;
 use super::D2;
 
 use super::vector::vector;
 use super::vector::Vector;
 
+use num_traits::{CheckedAdd, CheckedMul};
+
 use std::cmp::Ordering;
 use std::fmt;
+use std::iter;
 use std::ops;
 use std::slice;
 
-use std::error::Error;
-
 #[derive(Debug, Clone, PartialEq)]
 pub struct Matrix<K: FieldBound> {
-    vectors: Vector<Vector<K>>, // columns
+    data: Vec<K>, // column-major, entry (i, j) at j * stride + i
+    rows: usize,
+    cols: usize,
+    stride: usize,
 }
 
-#[derive(Debug)]
-pub struct InverseNotFound {}
+// Zero threshold for elimination: |x| <= max(absolute, relative * max |a_ij|).
+// A bare scalar converts to an absolute tolerance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tolerance<K: FieldBound> {
+    absolute: K,
+    relative: K,
+}
 
-impl fmt::Display for InverseNotFound {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", "inverse is not found")
+impl<K: FieldBound> Tolerance<K> {
+    pub fn new(absolute: K, relative: K) -> Self {
+        Tolerance { absolute, relative }
     }
-}
 
-impl Error for InverseNotFound {}
+    pub fn absolute(tol: K) -> Self {
+        Tolerance::new(tol, K::ZERO)
+    }
+
+    pub fn relative(tol: K) -> Self {
+        Tolerance::new(K::ZERO, tol)
+    }
 
-impl InverseNotFound {
-    fn new() -> Self {
-        InverseNotFound {}
+    // Relative eps * max(m, n), zero for exact fields
+    pub fn default_for(m: &Matrix<K>) -> Self {
+        let mut dim = K::ZERO;
+        for _ in 0..m.n_rows().max(m.n_cols()) {
+            dim += &K::ONE;
+        }
+        Tolerance::relative(K::epsilon() * dim)
+    }
+
+    pub fn threshold(&self, m: &Matrix<K>) -> K {
+        let relative = self.relative.clone() * m.max_abs();
+        if relative > self.absolute {
+            relative
+        } else {
+            self.absolute.clone()
+        }
+    }
+}
+
+impl<K: FieldBound> From<K> for Tolerance<K> {
+    fn from(tol: K) -> Self {
+        Tolerance::absolute(tol)
     }
 }
 
 fn perfect_square_root(length: usize) -> Option<usize> {
     let root = (length as f64).sqrt().trunc() as usize;
     if root * root == length {
-        return Some(root);
+        Some(root)
     } else {
-        return None;
+        None
     }
 }
 
@@ -81,7 +116,10 @@ macro_rules! matrix {
 
     ($($e:expr),+) => {
          {
-             Matrix::from([$($e),+].as_slice())
+             match $crate::matrix::Matrix::try_from([$($e),+].as_slice()) {
+                 Ok(m) => m,
+                 Err(e) => panic!("{}", e),
+             }
          }
     };
 
@@ -109,60 +147,90 @@ macro_rules! matrix {
 }
 pub use matrix;
 
-impl<K: FieldBound> From<&[K]> for Matrix<K> {
-    fn from(content: &[K]) -> Matrix<K> {
+impl<K: FieldBound> TryFrom<&[K]> for Matrix<K> {
+    type Error = MatrixError;
+
+    // Row-major entries of a square matrix
+    fn try_from(content: &[K]) -> Result<Matrix<K>, MatrixError> {
+        if content.is_empty() {
+            return Err(MatrixError::Empty { operation: "from" });
+        }
+
         match perfect_square_root(content.len()) {
             Some(root) => {
-                let v = content[0].clone();
-                let col = Vector::from([v].as_slice());
-                let mut columns = vec![col; root];
-                let mut counter: usize = 0;
-
-                for item in &mut columns {
-                    *item = Vector::from(&content[(counter * root)..((counter + 1) * root)]);
-                    counter += 1;
-                }
-
-                let mut m = Self {
-                    vectors: Vector::from(columns),
-                };
+                let mut m = Matrix::from_col_major(root, root, content.to_vec());
                 m.transpose_mut();
-                m
-            }
-            None => {
-                panic!("array cannot form a squared matrix");
+                Ok(m)
             }
+            None => Err(MatrixError::NotSquare {
+                operation: "from",
+                shape: Dimension::D1(D1 {
+                    length: content.len(),
+                }),
+            }),
         }
     }
 }
 
-impl<K: FieldBound> From<&[Vector<K>]> for Matrix<K> {
-    fn from(content: &[Vector<K>]) -> Matrix<K> {
+impl<K: FieldBound> TryFrom<&[Vector<K>]> for Matrix<K> {
+    type Error = MatrixError;
+
+    // One vector per row
+    fn try_from(content: &[Vector<K>]) -> Result<Matrix<K>, MatrixError> {
+        if content.is_empty() || content[0].is_empty() {
+            return Err(MatrixError::Empty { operation: "from" });
+        }
+
         if !is_rectangular!(content) {
-            panic!("array shapes are not uniform");
+            return Err(MatrixError::NotRectangular { operation: "from" });
         }
 
-        let mut m = Self {
-            vectors: Vector::from(content),
-        };
+        let mut data = Vec::with_capacity(content.len() * content[0].len());
+        for row in content {
+            data.extend(row.iter().cloned());
+        }
+
+        let mut m = Matrix::from_col_major(content[0].len(), content.len(), data);
         m.transpose_mut();
 
-        m
+        Ok(m)
     }
 }
 
+// One vector per column
 impl<K: FieldBound> From<Vector<Vector<K>>> for Matrix<K> {
     fn from(content: Vector<Vector<K>>) -> Matrix<K> {
-        Matrix { vectors: content }
+        match Matrix::try_from_cols(content) {
+            Ok(m) => m,
+            Err(e) => panic!("{}", e),
+        }
+    }
+}
+
+// Element access by (row, column)
+
+impl<K: FieldBound> ops::Index<(usize, usize)> for Matrix<K> {
+    type Output = K;
+    fn index(&self, (i, j): (usize, usize)) -> &K {
+        self.check_bounds(i, j);
+        &self.data[j * self.stride + i]
+    }
+}
+
+impl<K: FieldBound> ops::IndexMut<(usize, usize)> for Matrix<K> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut K {
+        self.check_bounds(i, j);
+        &mut self.data[j * self.stride + i]
     }
 }
 
+// Iterates over copies of the columns, see cols() for borrowed slices
 impl<'a, K: FieldBound> IntoIterator for &'a Matrix<K> {
-    type Item = &'a Vector<K>;
-    type IntoIter = slice::Iter<'a, Vector<K>>;
+    type Item = Vector<K>;
+    type IntoIter = iter::Map<slice::Chunks<'a, K>, fn(&'a [K]) -> Vector<K>>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.vectors.iter()
+        self.cols().map(Vector::from)
     }
 }
 
@@ -180,7 +248,7 @@ impl<K: fmt::Display + FieldBound> fmt::Display for Matrix<K> {
             let j: usize = j;
             for i in 0..dim.rows {
                 let i: usize = i;
-                str_mx[j][i] = format!("{:.2}", self.vectors[j][i]);
+                str_mx[j][i] = format!("{:.2}", self[(i, j)]);
                 col_len[j] = col_len[j].max(str_mx[j][i].len());
             }
         }
@@ -195,7 +263,7 @@ impl<K: fmt::Display + FieldBound> fmt::Display for Matrix<K> {
                 str_row.push_str(&format!("{:<width$}  ", str_mx[j][i]));
             }
             str_disp.push_str(&str_row);
-            str_disp.push_str("\n");
+            str_disp.push('\n');
         }
 
         write!(f, "{}", str_disp)
@@ -220,14 +288,12 @@ macro_rules! impl_ops_assign {
             K: FieldBound
         {
             fn $fun(&mut self, rhs: &Self) {
-                self.force_eq_shape(&rhs);
+                if self.shape() != rhs.shape() {
+                    panic!("{}", MatrixError::incompatible(stringify!($fun), self.shape(), rhs.shape()));
+                }
 
-                let mut m_iter = rhs.into_iter();
-                for col in &mut self.vectors {
-                    match m_iter.next() {
-                        Some(v_ref) => *col $op v_ref,
-                        None => {}
-                    }
+                for (x, y) in self.data.iter_mut().zip(&rhs.data) {
+                    *x $op y;
                 }
             }
         }
@@ -242,16 +308,22 @@ impl_ops_assign!(ops::DivAssign<&Self>, div_assign, /=);
 impl<K: FieldBound> ops::MulAssign<&Vector<K>> for Matrix<K> {
     // Column-wise multiplication
     fn mul_assign(&mut self, rhs: &Vector<K>) {
-        for col in &mut self.vectors {
-            *col *= rhs;
+        if rhs.len() != self.rows {
+            panic!("{}", MatrixError::size("mul_assign", self.rows, rhs.len()));
+        }
+
+        for col in self.data.chunks_mut(self.stride.max(1)) {
+            for (x, y) in col.iter_mut().zip(rhs) {
+                *x *= y;
+            }
         }
     }
 }
 
 impl<K: FieldBound> ops::MulAssign<&K> for Matrix<K> {
     fn mul_assign(&mut self, rhs: &K) {
-        for col in &mut self.vectors {
-            *col *= rhs;
+        for x in &mut self.data {
+            *x *= rhs;
         }
     }
 }
@@ -268,16 +340,13 @@ impl<K: FieldBound> VectorSpace for Matrix<K> {
 
     fn shape(&self) -> Dimension {
         Dimension::D2(D2 {
-            rows: self.vectors[0].size(),
-            cols: self.vectors.size(),
+            rows: self.rows,
+            cols: self.cols,
         })
     }
 
     fn size(&self) -> usize {
-        match self.shape() {
-            Dimension::D1(d) => d.length,
-            Dimension::D2(d) => d.rows * d.cols,
-        }
+        self.rows * self.cols
     }
 
     fn add(&mut self, v: &Self) {
@@ -293,19 +362,39 @@ impl<K: FieldBound> VectorSpace for Matrix<K> {
     }
 
     fn sum(&self) -> K {
-        self.vectors.sum().sum()
+        let mut sum = self.data[0].clone();
+        for x in &self.data[1..] {
+            sum += x;
+        }
+        sum
     }
 
     fn sqsum(&self) -> K {
-        self.vectors.sqsum().sum()
+        let mut sqsum = K::ZERO;
+        for x in &self.data {
+            let mut sq = x.clone();
+            sq *= &x.conjugate();
+            sqsum += &sq;
+        }
+        sqsum
     }
 
     fn norm_inf(&self) -> K {
-        self.vectors.norm_inf().norm_inf()
+        let mut max = self.data[0].abs();
+        for x in &self.data {
+            if x.abs() > max {
+                max = x.abs();
+            }
+        }
+        max
     }
 
     fn norm_1(&self) -> K {
-        self.vectors.norm_1().sum()
+        let mut sum = self.data[0].abs();
+        for x in &self.data[1..] {
+            sum += &x.abs();
+        }
+        sum
     }
 
     fn norm(&self) -> K {
@@ -314,347 +403,496 @@ impl<K: FieldBound> VectorSpace for Matrix<K> {
 }
 
 impl<K: FieldBound> Matrix<K> {
-    pub fn transpose_mut(&mut self) {
-        let pivot = self.vectors.clone();
-        self.vectors = Vector::from(vec![pivot[0].clone(); pivot[0].size()]);
-        for i in 0..pivot[0].size() {
-            let mut row = vec![pivot[0][0].clone(); pivot.size()];
-            for j in 0..pivot.size() {
-                row[j] = pivot[j][i].clone();
+    // Fallible form of Matrix::from(Vector<Vector<K>>), one vector per column
+    pub fn try_from_cols(content: Vector<Vector<K>>) -> Result<Self, MatrixError> {
+        let rows = if content.is_empty() {
+            0
+        } else {
+            content[0].len()
+        };
+        let mut data = Vec::with_capacity(rows * content.len());
+
+        for col in &content {
+            if col.len() != rows {
+                return Err(MatrixError::NotRectangular { operation: "from" });
             }
-            self.vectors[i] = Vector::from(row);
+            data.extend(col.iter().cloned());
         }
+
+        Ok(Matrix::from_col_major(rows, content.len(), data))
+    }
+
+    pub(crate) fn from_col_major(rows: usize, cols: usize, data: Vec<K>) -> Self {
+        debug_assert_eq!(data.len(), rows * cols);
+        Matrix {
+            data,
+            rows,
+            cols,
+            stride: rows,
+        }
+    }
+
+    fn check_bounds(&self, i: usize, j: usize) {
+        if i >= self.rows || j >= self.cols {
+            panic!(
+                "index ({}, {}) out of bounds for a {}x{} matrix",
+                i, j, self.rows, self.cols
+            );
+        }
+    }
+
+    // Column-major entries, column j starting at j * stride()
+    pub fn as_slice(&self) -> &[K] {
+        &self.data
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [K] {
+        &mut self.data
+    }
+
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    pub fn col(&self, j: usize) -> &[K] {
+        &self.data[j * self.stride..j * self.stride + self.rows]
+    }
+
+    pub fn cols(&self) -> slice::Chunks<'_, K> {
+        self.data.chunks(self.stride.max(1))
+    }
+
+    pub fn transpose_mut(&mut self) {
+        *self = self.transpose();
     }
 
     pub fn transpose(&self) -> Self {
+        let (m, n) = (self.rows, self.cols);
+        let mut data = Vec::with_capacity(m * n);
+
+        // Column j of the transpose is row j of self
+        for i in 0..m {
+            for j in 0..n {
+                data.push(self.data[j * self.stride + i].clone());
+            }
+        }
+
+        Matrix::from_col_major(n, m, data)
+    }
+
+    pub fn conjugate(&self) -> Self {
         let mut m = self.clone();
+        for x in &mut m.data {
+            *x = x.conjugate();
+        }
+        m
+    }
+
+    // Hermitian adjoint
+    pub fn conjugate_transpose(&self) -> Self {
+        let mut m = self.conjugate();
         m.transpose_mut();
         m
     }
 
     pub fn is_square(&self) -> bool {
-        let shape = self.shape().d2().unwrap();
-        shape.rows == shape.cols
+        self.rows == self.cols
     }
 
-    pub fn append_col(&mut self, v: Vector<K>) {
-        if self.size() != 0 && self.vectors[0].len() != v.len() {
-            panic!("cannot append incompatible column");
+    pub(crate) fn check_square(&self, operation: &'static str) -> Result<(), MatrixError> {
+        if self.size() == 0 {
+            return Err(MatrixError::Empty { operation });
+        }
+        if !self.is_square() {
+            return Err(MatrixError::NotSquare {
+                operation,
+                shape: self.shape(),
+            });
         }
-        self.vectors.append(v);
+        Ok(())
     }
 
-    pub fn bind_cols(&mut self, m: Matrix<K>) {
-        for col in &m {
-            self.append_col(col.clone());
+    pub fn append_col(&mut self, v: Vector<K>) {
+        if let Err(e) = self.try_append_col(v) {
+            panic!("{}", e);
         }
     }
 
-    fn n_rows(&self) -> usize {
+    pub fn try_append_col(&mut self, v: Vector<K>) -> Result<(), MatrixError> {
+        if self.size() != 0 && self.rows != v.len() {
+            return Err(MatrixError::size("append_col", self.n_rows(), v.len()));
+        }
         if self.size() == 0 {
-            return 0;
-        } else {
-            return self.vectors[0].len();
+            self.rows = v.len();
+            self.stride = v.len();
         }
+        self.data.extend(v.iter().cloned());
+        self.cols += 1;
+        Ok(())
     }
 
-    fn n_cols(&self) -> usize {
-        self.vectors.len()
+    pub fn bind_cols(&mut self, m: Matrix<K>) {
+        if let Err(e) = self.try_bind_cols(m) {
+            panic!("{}", e);
+        }
     }
 
-    #[allow(dead_code)]
-    fn swap_cols(&mut self, inx1: usize, inx2: usize) {
-        let cl1 = self.vectors[inx1].clone();
-        self.vectors[inx1] = self.vectors[inx2].clone();
-        self.vectors[inx2] = cl1;
+    pub fn try_bind_cols(&mut self, m: Matrix<K>) -> Result<(), MatrixError> {
+        if self.size() != 0 && self.n_rows() != m.n_rows() {
+            return Err(MatrixError::incompatible(
+                "bind_cols",
+                Dimension::D2(D2 {
+                    rows: self.n_rows(),
+                    cols: m.n_cols(),
+                }),
+                m.shape(),
+            ));
+        }
+        if self.size() == 0 {
+            *self = m;
+            return Ok(());
+        }
+        for col in m.cols() {
+            self.data.extend_from_slice(&col[..m.rows]);
+        }
+        self.cols += m.cols;
+        Ok(())
     }
 
-    fn swap_rows(&mut self, inx1: usize, inx2: usize) {
-        let cl1 = self.clone_row(inx1);
-        self.set_row(inx1, &self.clone_row(inx2));
-        self.set_row(inx2, &cl1);
+    pub fn n_rows(&self) -> usize {
+        self.rows
     }
 
-    fn clone_row(&self, inx: usize) -> Vector<K> {
-        let k_init = self.vectors[0][inx].clone();
-        let mut cl = vector![k_init; self.vectors.len()];
+    pub fn n_cols(&self) -> usize {
+        self.cols
+    }
 
-        for i in 1..self.vectors.len() {
-            cl[i] = self.vectors[i][inx].clone();
+    pub(crate) fn swap_cols(&mut self, inx1: usize, inx2: usize) {
+        if inx1 == inx2 {
+            return;
+        }
+        for i in 0..self.rows {
+            self.data
+                .swap(inx1 * self.stride + i, inx2 * self.stride + i);
         }
-
-        cl
     }
 
-    fn set_row(&mut self, inx: usize, v: &Vector<K>) {
-        if v.len() != self.vectors.len() {
-            panic!("incorrect row size");
+    pub(crate) fn swap_rows(&mut self, inx1: usize, inx2: usize) {
+        if inx1 == inx2 {
+            return;
         }
-
-        for i in 0..self.vectors.len() {
-            self.vectors[i][inx] = v[i].clone();
+        for j in 0..self.cols {
+            self.data
+                .swap(j * self.stride + inx1, j * self.stride + inx2);
         }
     }
 
-    pub fn col_sum(&self) -> Vector<K>
-    where
-        K: FieldBound,
-    {
-        let k_init = self.vectors[0][0].clone();
-        let mut col_sums = vector![k_init; self.vectors.len()];
-
-        for i in 0..self.vectors.len() {
-            col_sums[i] = self.vectors[i].sum();
+    pub fn col_sum(&self) -> Vector<K> {
+        let mut col_sums = vec![];
+        for col in self.cols() {
+            col_sums.push(Vector::from(col).sum());
         }
 
-        col_sums
+        Vector::from(col_sums)
     }
 
-    pub fn mul_vec(&self, v: &Vector<K>) -> Vector<K>
-    where
-        K: FieldBound,
-    {
-        let mut m = self.clone();
-        m.transpose_mut();
-        //ops::MulAssign::mul_assign(self, v); // Cannot use self *= v ?
-        m *= v;
-        m.col_sum()
+    pub fn mul_vec(&self, v: &Vector<K>) -> Vector<K> {
+        match self.try_mul_vec(v) {
+            Ok(u) => u,
+            Err(e) => panic!("{}", e),
+        }
     }
 
-    pub fn mul_mat(&self, m: &Matrix<K>) -> Matrix<K>
-    where
-        K: FieldBound,
-    {
-        if self.size() == 0 || m.size() == 0 {
-            panic!("cannot compute matrix multiplication of an empty matrix");
+    pub fn try_mul_vec(&self, v: &Vector<K>) -> Result<Vector<K>, MatrixError> {
+        if self.size() == 0 {
+            return Err(MatrixError::Empty {
+                operation: "mul_vec",
+            });
         }
-
-        let sh_m = m.shape().d2().unwrap();
-        let sh_self = self.shape().d2().unwrap();
-
-        if sh_self.cols != sh_m.rows {
-            panic!("matrices are incompatible for matrix multiplication");
+        if v.len() != self.n_cols() {
+            return Err(MatrixError::size("mul_vec", self.n_cols(), v.len()));
         }
 
-        let k_init = self.vectors[0][0].clone();
-        let inv_n_row = sh_m.cols;
-        let inv_n_col = sh_self.rows;
-        let mut result = matrix![k_init; inv_n_row, inv_n_col];
-
-        let mut cl = self.clone();
-        cl.transpose_mut();
-
-        for j1 in 0..inv_n_col {
-            // itr over cols of cl
-            for j2 in 0..inv_n_row {
-                // itr over cols of m
-                result.vectors[j1][j2] = cl.vectors[j1].dot(&m.vectors[j2]);
+        // Accumulate v_j times column j
+        let mut y = vec![K::ZERO; self.rows];
+        for (col, v_j) in self.cols().zip(v) {
+            for (y_i, a_ij) in y.iter_mut().zip(col) {
+                *y_i += &(a_ij.clone() * v_j.clone());
             }
         }
 
-        result.transpose_mut();
-        result
+        Ok(Vector::from(y))
     }
 
-    pub fn trace(&self) -> K {
-        if !self.is_square() {
-            panic!("cannot compute trace of a non-square matrix");
+    pub fn mul_mat(&self, m: &Matrix<K>) -> Matrix<K> {
+        match self.try_mul_mat(m) {
+            Ok(p) => p,
+            Err(e) => panic!("{}", e),
         }
+    }
 
-        if self.size() == 0 {
-            panic!("cannot compute trace of an empty matrix");
+    fn check_mul_mat(&self, operation: &'static str, m: &Matrix<K>) -> Result<(), MatrixError> {
+        if self.size() == 0 || m.size() == 0 {
+            return Err(MatrixError::Empty { operation });
         }
 
-        let mut sum = self.vectors[0][0].clone();
-
-        for i in 1..self.vectors.len() {
-            sum += &self.vectors[i][i];
+        if self.cols != m.rows {
+            return Err(MatrixError::incompatible(
+                operation,
+                Dimension::D2(D2 {
+                    rows: self.cols,
+                    cols: m.cols,
+                }),
+                m.shape(),
+            ));
         }
-
-        sum
+        Ok(())
     }
 
-    pub fn row_echelon(&self) -> Matrix<K> {
-        // Return reduced row-echelon form
+    pub fn try_mul_mat(&self, m: &Matrix<K>) -> Result<Matrix<K>, MatrixError> {
+        self.check_mul_mat("mul_mat", m)?;
 
-        fn find_nonzero_from<K: FieldBound>(col: &Vector<K>, start: usize) -> usize {
-            for i in start..col.len() {
-                if !col[i].is_zero() {
-                    return i;
+        // Column j of the product is self times column j of m, built as
+        // a sum of scaled columns of self so every access is contiguous
+        let mut data = vec![K::ZERO; self.rows * m.cols];
+        for (out, m_col) in data.chunks_mut(self.rows).zip(m.cols()) {
+            for (a_col, b_kj) in self.cols().zip(m_col) {
+                for (c_ij, a_ik) in out.iter_mut().zip(a_col) {
+                    *c_ij += &(a_ik.clone() * b_kj.clone());
                 }
             }
-            start
         }
 
-        fn div_row_from<K: FieldBound>(m: &mut Matrix<K>, i: usize, from: usize, div: K) {
-            for j in from..m.n_cols() {
-                m.vectors[j][i] /= &div;
+        Ok(Matrix::from_col_major(self.rows, m.cols, data))
+    }
+
+    // Binary exponentiation, at most 2 log2(n) products and no squaring past
+    // the highest bit. Stops at the first product that fails.
+    fn power_by_squaring(
+        &self,
+        mut n: u64,
+        mul: impl Fn(&Matrix<K>, &Matrix<K>) -> Result<Matrix<K>, MatrixError>,
+    ) -> Result<Matrix<K>, MatrixError> {
+        let mut result: Option<Matrix<K>> = None;
+        let mut base = self.clone();
+
+        while n > 0 {
+            if n & 1 == 1 {
+                result = Some(match result {
+                    Some(r) => mul(&r, &base)?,
+                    None => base.clone(),
+                });
+            }
+            n >>= 1;
+            if n > 0 {
+                base = mul(&base, &base)?;
             }
         }
 
-        fn clone_row_from<K: FieldBound>(m: &Matrix<K>, i: usize, from: usize) -> Vector<K> {
-            let len = m.vectors.len() - from;
-            let mut v = vector![m.vectors[from][i].clone(); len];
-
-            let mut inner_count: usize = 1;
-            for j in (from + 1)..m.vectors.len() {
-                v[inner_count] = m.vectors[j][i].clone();
-                inner_count += 1;
-            }
+        Ok(result.unwrap_or_else(|| self.identity()))
+    }
 
-            v
+    // Negative exponents raise inverse() to |n|; for integer matrices that
+    // needs det = +-1
+    pub fn pow(&self, n: i64) -> Matrix<K> {
+        match self.try_pow(n) {
+            Ok(m) => m,
+            Err(e) => panic!("{}", e),
         }
+    }
 
-        fn sub_row_from<K>(m: &mut Matrix<K>, i: usize, from: usize, v: Vector<K>)
-        where
-            K: FieldBound,
-        {
-            let mut vec_count = 0;
-            for j in from..m.n_cols() {
-                m.vectors[j][i] -= &v[vec_count];
-                vec_count += 1;
-            }
+    pub fn try_pow(&self, n: i64) -> Result<Matrix<K>, MatrixError> {
+        self.check_square("pow")?;
+
+        let base = if n < 0 { self.inverse()? } else { self.clone() };
+        base.power_by_squaring(n.unsigned_abs(), |a, b| a.try_mul_mat(b))
+    }
+
+    pub fn trace(&self) -> K {
+        match self.try_trace() {
+            Ok(t) => t,
+            Err(e) => panic!("{}", e),
         }
+    }
 
-        fn neutralize_rows_until<K>(m: &mut Matrix<K>, until: usize, at_col: usize, v: &Vector<K>)
-        where
-            K: FieldBound,
-        {
-            for i in 0..until {
-                if m.vectors[at_col][i].is_zero() {
-                    continue;
-                }
+    pub fn try_trace(&self) -> Result<K, MatrixError> {
+        self.check_square("trace")?;
 
-                let mut v_scl = v.clone();
-                v_scl *= &m.vectors[at_col][i];
+        let mut sum = self[(0, 0)].clone();
 
-                sub_row_from(m, i, at_col, v_scl);
-            }
+        for i in 1..self.cols {
+            sum += &self[(i, i)];
         }
 
-        let mut rech = self.clone();
-
-        let mut i = 0;
-        for j in 0..rech.n_cols() {
-            // iterate over columns
-            let arg = find_nonzero_from(&rech.vectors[j], i);
+        Ok(sum)
+    }
 
-            if arg != i {
-                rech.swap_rows(arg, i);
+    fn max_abs(&self) -> K {
+        let mut max = K::ZERO;
+        for x in &self.data {
+            if x.abs() > max {
+                max = x.abs();
             }
+        }
+        max
+    }
 
-            if rech.vectors[j][i].is_zero() {
-                continue;
-            }
+    // Rounding threshold scaled to the matrix: eps * max(m, n) * max |a_ij|.
+    // Zero for exact fields.
+    pub fn default_tolerance(&self) -> K {
+        Tolerance::default_for(self).threshold(self)
+    }
 
-            let f = rech.vectors[j][i].clone();
-            div_row_from(&mut rech, i, j, f);
+    pub fn row_echelon(&self) -> Matrix<K> {
+        self.row_echelon_with_tol(self.default_tolerance())
+    }
 
-            // =================================================
-            // Backtracing for reduced row-echelon form
-            let partial_row_i = clone_row_from(&rech, i, j);
-            neutralize_rows_until(&mut rech, i, j, &partial_row_i);
-            // =================================================
+    pub fn row_echelon_with_tol(&self, tol: impl Into<Tolerance<K>>) -> Matrix<K> {
+        // Return reduced row-echelon form, entries with |x| <= tol count as zero
+        let tol = tol.into().threshold(self);
+        let mut rech = self.clone();
+        let (m, n, s) = (rech.rows, rech.cols, rech.stride);
 
-            if i == rech.n_rows() - 1 {
+        let mut i = 0;
+        for j in 0..n {
+            if i == m {
                 break;
             }
 
-            for i_bp in (i + 1)..rech.n_rows() {
-                let f = rech.vectors[j][i_bp].clone();
-                let mut scl_part_row_i = partial_row_i.clone();
-                scl_part_row_i *= f;
-                sub_row_from(&mut rech, i_bp, j, scl_part_row_i);
+            // Partial pivoting on column j
+            let mut arg = i;
+            for r in (i + 1)..m {
+                if rech.data[j * s + r].abs() > rech.data[j * s + arg].abs() {
+                    arg = r;
+                }
             }
-            i += 1;
-        }
-        rech
-    }
-
-    fn discard(&self, row_index: usize, col_index: usize) -> Matrix<K> {
-        let sh_self = self.shape().d2().unwrap();
-        let mut m = matrix![self.vectors[0][0].clone(); sh_self.rows - 1, sh_self.cols - 1];
-        let sh_m = m.shape().d2().unwrap();
 
-        let mut outer_i: usize = 0;
-        for i in 0..sh_m.rows {
-            if i == row_index {
-                outer_i += 1;
+            if rech.data[j * s + arg].abs() <= tol {
+                for r in i..m {
+                    rech.data[j * s + r] = K::ZERO;
+                }
+                continue;
             }
 
-            let mut outer_j: usize = 0;
+            rech.swap_rows(arg, i);
 
-            for j in 0..sh_m.cols {
-                if j == col_index {
-                    outer_j += 1;
-                }
-
-                m.vectors[j][i] = self.vectors[outer_j][outer_i].clone();
+            let f = rech.data[j * s + i].clone();
+            for jj in j..n {
+                rech.data[jj * s + i] /= &f;
+            }
 
-                outer_j += 1;
+            // Clear column j above and below the pivot
+            let factors = rech.data[j * s..j * s + m].to_vec();
+            for jj in (j + 1)..n {
+                let p = rech.data[jj * s + i].clone();
+                if p.is_zero() {
+                    continue;
+                }
+                for (r, f) in factors.iter().enumerate() {
+                    if r != i && !f.is_zero() {
+                        rech.data[jj * s + r] -= &(f.clone() * p.clone());
+                    }
+                }
+            }
+            for r in 0..m {
+                if r != i {
+                    rech.data[j * s + r] = K::ZERO;
+                }
             }
-            outer_i += 1;
+
+            i += 1;
         }
 
-        return m;
+        rech
     }
 
     fn determinant_sq_lt2(&self) -> K {
         match self.shape().d2().unwrap() {
             D2 { rows: 2, cols: 2 } => {
-                return self.vectors[0][0].clone() * self.vectors[1][1].clone()
-                    - self.vectors[0][1].clone() * self.vectors[1][0].clone()
+                self[(0, 0)].clone() * self[(1, 1)].clone()
+                    - self[(1, 0)].clone() * self[(0, 1)].clone()
             }
-            D2 { rows: 1, cols: 1 } => return self.vectors[0][0].clone(),
+            D2 { rows: 1, cols: 1 } => self[(0, 0)].clone(),
             _ => panic!("incorrect dimensions"),
         }
     }
 
     pub fn determinant(&self) -> K {
-        if !self.is_square() {
-            panic!("determinant is not implemented for non-square matrices");
+        match self.try_determinant() {
+            Ok(d) => d,
+            Err(e) => panic!("{}", e),
         }
-        match self.shape().d2().unwrap().rows.cmp(&2) {
-            Ordering::Less => return self.determinant_sq_lt2(),
-            Ordering::Equal => return self.determinant_sq_lt2(),
+    }
+
+    pub fn try_determinant(&self) -> Result<K, MatrixError> {
+        self.check_square("determinant")?;
+
+        let det = match self.shape().d2().unwrap().rows.cmp(&2) {
+            Ordering::Less => self.determinant_sq_lt2(),
+            Ordering::Equal => self.determinant_sq_lt2(),
             Ordering::Greater => {
-                let mut det: K = self.vectors[0][0].clone();
-
-                for j in 0..self.vectors.len() {
-                    let l_det = self.vectors[j][0].clone() * self.discard(0, j).determinant();
-                    if j == 0 {
-                        det = l_det;
-                    } else if j % 2 == 0 {
-                        det += &l_det;
-                    } else {
-                        det -= &l_det;
+                if K::INTEGRAL {
+                    self.determinant_bareiss()
+                } else {
+                    self.lu().determinant()
+                }
+            }
+        };
+
+        Ok(det)
+    }
+
+    fn determinant_bareiss(&self) -> K {
+        // Fraction-free elimination: every division below is exact,
+        // so integer fields keep an exact determinant.
+        let n = self.n_rows();
+        let mut m = self.clone();
+        let mut sign = K::ONE;
+        let mut prev = K::ONE;
+
+        for k in 0..(n - 1) {
+            if m[(k, k)].is_zero() {
+                match ((k + 1)..n).find(|&i| !m[(i, k)].is_zero()) {
+                    Some(i) => {
+                        m.swap_rows(i, k);
+                        sign = -sign;
                     }
+                    None => return K::ZERO,
                 }
+            }
 
-                return det;
+            for i in (k + 1)..n {
+                for j in (k + 1)..n {
+                    let mut v = m[(i, j)].clone() * m[(k, k)].clone()
+                        - m[(i, k)].clone() * m[(k, j)].clone();
+                    v /= &prev;
+                    m[(i, j)] = v;
+                }
             }
+            prev = m[(k, k)].clone();
         }
+
+        sign * m[(n - 1, n - 1)].clone()
     }
 
     pub fn identity(&self) -> Matrix<K> {
-        if !self.is_square() {
-            panic!("identity is not applicable for a non-square matrix");
+        match self.try_identity() {
+            Ok(m) => m,
+            Err(e) => panic!("{}", e),
         }
+    }
 
-        let mut m = self.clone();
+    pub fn try_identity(&self) -> Result<Matrix<K>, MatrixError> {
+        self.check_square("identity")?;
 
-        for j in 0..self.vectors.len() {
-            for i in 0..self.vectors.len() {
-                let val = m.vectors[j][i].clone();
-                if j == i {
-                    m.vectors[j][i] /= &val;
-                } else {
-                    m.vectors[j][i] -= &val;
-                }
-            }
+        let n = self.cols;
+        let mut m = matrix![K::ZERO; n];
+
+        for i in 0..n {
+            m[(i, i)] = K::ONE;
         }
 
-        m
+        Ok(m)
     }
 
     pub fn is_identity(&self) -> bool {
@@ -663,48 +901,161 @@ impl<K: FieldBound> Matrix<K> {
         self == &identity
     }
 
-    pub fn inverse(&self) -> Result<Matrix<K>, InverseNotFound> {
-        let mut combined = self.clone();
+    pub fn inverse(&self) -> Result<Matrix<K>, MatrixError> {
+        self.inverse_with_tol(self.default_tolerance())
+    }
 
-        combined.bind_cols(self.identity());
+    // Integer matrices are inverted exactly and only when det = +-1, the
+    // tolerance is not used for them
+    pub fn inverse_with_tol(&self, tol: impl Into<Tolerance<K>>) -> Result<Matrix<K>, MatrixError> {
+        self.check_square("inverse")?;
+        if K::INTEGRAL {
+            return self.unimodular_inverse();
+        }
+        self.try_lu_with_tol(tol)?.inverse()
+    }
 
-        let reduced = combined.row_echelon();
-        let first_half = Matrix::from(Vector::from(reduced.vectors.slice(0..self.vectors.len())));
-        let second_half = Matrix::from(Vector::from(
-            reduced
-                .vectors
-                .slice(self.vectors.len()..combined.vectors.len()),
-        ));
+    // A^{-1} = adj(A) / det(A), integral exactly when det(A) = +-1
+    fn unimodular_inverse(&self) -> Result<Matrix<K>, MatrixError> {
+        let det = self.try_determinant()?;
+        if det != K::ONE && det != -K::ONE {
+            return Err(MatrixError::NotInvertible);
+        }
 
-        if first_half.is_identity() {
-            Ok(second_half)
-        } else {
-            Err(InverseNotFound::new())
+        let n = self.n_rows();
+        let mut inv = matrix![K::ZERO; n];
+        if n == 1 {
+            inv[(0, 0)] = det;
+            return Ok(inv);
         }
-    }
 
-    pub fn rank(&self) -> usize {
-        fn is_null_row<K: FieldBound>(m: &Matrix<K>, row_index: usize) -> bool {
-            for j in 0..m.vectors.len() {
-                let zero = m.vectors[j][row_index].clone() - m.vectors[j][row_index].clone();
-                if m.vectors[j][row_index] != zero {
-                    return false;
+        // inv_ji = det * (-1)^(i + j) det(A without row i and column j)
+        for i in 0..n {
+            for j in 0..n {
+                let mut data = Vec::with_capacity((n - 1) * (n - 1));
+                for c in (0..n).filter(|&c| c != j) {
+                    for r in (0..n).filter(|&r| r != i) {
+                        data.push(self[(r, c)].clone());
+                    }
                 }
+                let minor = Matrix::from_col_major(n - 1, n - 1, data).determinant();
+                let cofactor = if (i + j) % 2 == 0 { minor } else { -minor };
+                inv[(j, i)] = cofactor * det.clone();
             }
-            true
         }
 
-        let mut null_rows: usize = 0;
-        let shape = self.shape().d2().unwrap();
-        let m = self.row_echelon();
+        Ok(inv)
+    }
+
+    pub fn solve(&self, b: &Vector<K>) -> Result<Vector<K>, MatrixError> {
+        if b.len() != self.n_rows() {
+            return Err(MatrixError::size("solve", self.n_rows(), b.len()));
+        }
+
+        let x = self.solve_many(&Matrix::from(vector![b.clone()]))?;
+        Ok(Vector::from(x.col(0)))
+    }
+
+    pub fn solve_many(&self, b: &Matrix<K>) -> Result<Matrix<K>, MatrixError> {
+        if b.n_rows() != self.n_rows() {
+            return Err(MatrixError::incompatible(
+                "solve_many",
+                Dimension::D2(D2 {
+                    rows: self.n_rows(),
+                    cols: b.n_cols(),
+                }),
+                b.shape(),
+            ));
+        }
 
-        for i in 0..shape.rows {
-            if is_null_row(&m, i) {
-                null_rows += 1;
+        if self.is_square() {
+            let lu = self.try_lu()?;
+            if !lu.is_singular() {
+                return lu.solve_many(b);
             }
         }
 
-        shape.rows - null_rows
+        self.solve_echelon(b)
+    }
+
+    fn solve_echelon(&self, b: &Matrix<K>) -> Result<Matrix<K>, MatrixError> {
+        // Classify a singular or non-square system through the reduced
+        // row-echelon form of [A | B]
+        let n = self.n_cols();
+        let mut combined = self.clone();
+        combined.bind_cols(b.clone());
+
+        let tol = combined.default_tolerance();
+        let rech = combined.row_echelon_with_tol(tol.clone());
+        let mut pivots: Vec<(usize, usize)> = vec![];
+
+        for i in 0..rech.n_rows() {
+            match (0..rech.n_cols()).find(|&j| rech[(i, j)].abs() > tol) {
+                Some(j) if j >= n => return Err(MatrixError::Inconsistent),
+                Some(j) => pivots.push((i, j)),
+                None => {}
+            }
+        }
+
+        if pivots.len() < n {
+            return Err(MatrixError::Singular);
+        }
+
+        let mut x = matrix![K::ZERO; n, b.n_cols()];
+        for (i, j) in pivots {
+            for k in 0..b.n_cols() {
+                x[(j, k)] = rech[(i, n + k)].clone();
+            }
+        }
+
+        Ok(x)
+    }
+
+    pub fn rank(&self) -> usize {
+        self.rank_with_tol(self.default_tolerance())
+    }
+
+    pub fn rank_with_tol(&self, tol: impl Into<Tolerance<K>>) -> usize {
+        self.elimination_rank(tol.into().threshold(self))
+    }
+}
+
+// Overflow-checked products for the primitive integer types
+
+impl<K> Matrix<K>
+where
+    K: FieldBound + CheckedAdd + CheckedMul,
+{
+    // Err(Overflow) if any entry overflows, shape errors as in try_mul_mat
+    pub fn checked_mul_mat(&self, m: &Matrix<K>) -> Result<Matrix<K>, MatrixError> {
+        self.checked_product("checked_mul_mat", m)
+    }
+
+    pub fn checked_pow(&self, n: u64) -> Result<Matrix<K>, MatrixError> {
+        self.check_square("checked_pow")?;
+        self.power_by_squaring(n, |a, b| a.checked_product("checked_pow", b))
+    }
+
+    fn checked_product(
+        &self,
+        operation: &'static str,
+        m: &Matrix<K>,
+    ) -> Result<Matrix<K>, MatrixError> {
+        self.check_mul_mat(operation, m)?;
+
+        let mut data = vec![K::ZERO; self.rows * m.cols];
+        for (out, m_col) in data.chunks_mut(self.rows).zip(m.cols()) {
+            for (a_col, b_kj) in self.cols().zip(m_col) {
+                for (c_ij, a_ik) in out.iter_mut().zip(a_col) {
+                    *c_ij = a_ik
+                        .checked_mul(b_kj)
+                        .and_then(|p| c_ij.checked_add(&p))
+                        .ok_or(MatrixError::Overflow { operation })?;
+                }
+            }
+        }
+
+        Ok(Matrix::from_col_major(self.rows, m.cols, data))
     }
 }
 
@@ -716,7 +1067,7 @@ where
     K: FieldBound,
 {
     let mut slide = v.clone();
-    slide.sub(&u);
+    slide.sub(u);
     slide.scl(t);
 
     let mut interp = u.clone();
@@ -725,10 +1076,14 @@ where
     interp
 }
 
+// The tests spell out identities and patterns on purpose
 #[cfg(test)]
+#[allow(clippy::identity_op, clippy::unnecessary_cast)]
 mod tests {
 
     use super::*;
+    use crate::approx::assert_matrix_approx_eq;
+    use crate::complex::Complex;
 
     #[test]
     fn matrix_macro_test() {
@@ -740,37 +1095,44 @@ mod tests {
         assert_eq!(
             m1,
             Matrix {
-                vectors: Vector::from(vec![
-                    Vector::from(vec![1, 4]), // 1st column
-                    Vector::from(vec![2, 5]), // 2nd column
-                    Vector::from(vec![3, 6]), // 3rd column
-                ])
+                data: vec![
+                    1, 4, // 1st column
+                    2, 5, // 2nd column
+                    3, 6, // 3rd column
+                ],
+                rows: 2,
+                cols: 3,
+                stride: 2,
             }
         );
 
         assert_eq!(
             m2,
             Matrix {
-                vectors: Vector::from(vec![Vector::from(vec![1, 3]), Vector::from(vec![2, 4]),])
+                data: vec![1, 3, 2, 4],
+                rows: 2,
+                cols: 2,
+                stride: 2,
             }
         );
 
         assert_eq!(
             m3,
             Matrix {
-                vectors: Vector::from(
-                    vec![Vector::from(vec![-1, -1]), Vector::from(vec![-1, -1]),]
-                )
+                data: vec![-1, -1, -1, -1],
+                rows: 2,
+                cols: 2,
+                stride: 2,
             }
         );
 
         assert_eq!(
             m4,
             Matrix {
-                vectors: Vector::from(vec![
-                    Vector::from(vec![0, 0, 0]),
-                    Vector::from(vec![0, 0, 0])
-                ])
+                data: vec![0, 0, 0, 0, 0, 0],
+                rows: 3,
+                cols: 2,
+                stride: 3,
             }
         )
     }
@@ -786,22 +1148,26 @@ mod tests {
 
     #[test]
     fn transpose_test() {
-        let mut m1 = Matrix::from([1, 2, 3, 4].as_slice());
-        let mut m2 = Matrix::from([1, 2, 3, 4, 5, 6, 7, 8, 9].as_slice());
+        let mut m1 = Matrix::try_from([1, 2, 3, 4].as_slice()).unwrap();
+        let mut m2 = Matrix::try_from([1, 2, 3, 4, 5, 6, 7, 8, 9].as_slice()).unwrap();
 
         m1.transpose_mut();
         m2.transpose_mut();
 
-        assert_eq!(m1, Matrix::from([1, 3, 2, 4].as_slice()));
-        assert_eq!(m2, Matrix::from([1, 4, 7, 2, 5, 8, 3, 6, 9].as_slice()));
+        assert_eq!(m1, Matrix::try_from([1, 3, 2, 4].as_slice()).unwrap());
+        assert_eq!(
+            m2,
+            Matrix::try_from([1, 4, 7, 2, 5, 8, 3, 6, 9].as_slice()).unwrap()
+        );
     }
 
     #[test]
     fn shape_test() {
-        let m1 = Matrix::from([1, 2, 3, 4].as_slice());
-        let m2 = Matrix::from([1, 2, 3, 4, 5, 6, 7, 8, 9].as_slice());
+        let m1 = Matrix::try_from([1, 2, 3, 4].as_slice()).unwrap();
+        let m2 = Matrix::try_from([1, 2, 3, 4, 5, 6, 7, 8, 9].as_slice()).unwrap();
         let m3: Matrix<i32> =
-            Matrix::from([Vector::from(vec![1, 2, 3]), Vector::from(vec![3, 4, 5])].as_slice());
+            Matrix::try_from([Vector::from(vec![1, 2, 3]), Vector::from(vec![3, 4, 5])].as_slice())
+                .unwrap();
 
         assert_eq!(m1.shape(), Dimension::D2(D2 { rows: 2, cols: 2 }));
         assert_eq!(m2.shape(), Dimension::D2(D2 { rows: 3, cols: 3 }));
@@ -810,8 +1176,8 @@ mod tests {
 
     #[test]
     fn size_test() {
-        let m1 = Matrix::from([1, 2, 3, 4].as_slice());
-        let m2 = Matrix::from([1, 2, 3, 4, 5, 6, 7, 8, 9].as_slice());
+        let m1 = Matrix::try_from([1, 2, 3, 4].as_slice()).unwrap();
+        let m2 = Matrix::try_from([1, 2, 3, 4, 5, 6, 7, 8, 9].as_slice()).unwrap();
 
         assert_eq!(m1.size(), 4);
         assert_eq!(m2.size(), 9);
@@ -825,17 +1191,21 @@ mod tests {
     }
 
     #[test]
-    fn swap_cols_test() {
-        let mut m = matrix![[1, 2, 3], [4, 5, 6], [7, 8, 9]];
-        m.swap_cols(0, 2);
-        assert_eq!(m, matrix![[3, 2, 1], [6, 5, 4], [9, 8, 7]]);
+    fn cols_test() {
+        let m = matrix!([1, 2, 3], [4, 5, 6]);
+
+        let cols: Vec<&[i32]> = m.cols().collect();
+        assert_eq!(cols, vec![&[1, 4][..], &[2, 5], &[3, 6]]);
+
+        let cols: Vec<Vector<i32>> = (&m).into_iter().collect();
+        assert_eq!(cols, vec![vector![1, 4], vector![2, 5], vector![3, 6]]);
     }
 
     #[test]
-    fn set_row_test() {
+    fn swap_cols_test() {
         let mut m = matrix![[1, 2, 3], [4, 5, 6], [7, 8, 9]];
-        m.set_row(1, &vector![40, 50, 60]);
-        assert_eq!(m, matrix![[1, 2, 3], [40, 50, 60], [7, 8, 9]]);
+        m.swap_cols(0, 2);
+        assert_eq!(m, matrix![[3, 2, 1], [6, 5, 4], [9, 8, 7]]);
     }
 
     #[test]
@@ -917,22 +1287,44 @@ mod tests {
             u.mul_mat(&v),
             matrix![[74, 80, 86, 92], [173, 188, 203, 218]]
         );
+
+        // 0 * inf is NaN and must reach the product
+        let p = matrix![[f64::INFINITY, 1.]].mul_mat(&matrix![[0.], [2.]]);
+        assert!(p[(0, 0)].is_nan());
     }
 
     #[test]
-    fn trace_test() {
-        let m = matrix![[2, -5, 0], [4, 3, 7], [-2, 3, 4]];
+    fn conjugate_transpose_test() {
+        let m = matrix![
+            [Complex::new(1., 1.), Complex::new(2., 0.)],
+            [Complex::new(0., -3.), Complex::new(4., 5.)]
+        ];
 
-        assert_eq!(m.trace(), 9);
+        assert_eq!(
+            m.conjugate(),
+            matrix![
+                [Complex::new(1., -1.), Complex::new(2., 0.)],
+                [Complex::new(0., 3.), Complex::new(4., -5.)]
+            ]
+        );
+        assert_eq!(
+            m.conjugate_transpose(),
+            matrix![
+                [Complex::new(1., -1.), Complex::new(0., 3.)],
+                [Complex::new(2., 0.), Complex::new(4., -5.)]
+            ]
+        );
+        assert_eq!(
+            matrix![[1, 2], [3, 4]].conjugate_transpose(),
+            matrix![[1, 3], [2, 4]]
+        );
     }
 
     #[test]
-    fn discard_test() {
-        let m = matrix![[1, 2, 3], [4, 5, 6], [7, 8, 9]];
+    fn trace_test() {
+        let m = matrix![[2, -5, 0], [4, 3, 7], [-2, 3, 4]];
 
-        assert_eq!(m.discard(0, 0), matrix![[5, 6], [8, 9]]);
-        assert_eq!(m.discard(0, 1), matrix![[4, 6], [7, 9]]);
-        assert_eq!(m.discard(1, 2), matrix![[1, 2], [7, 8]]);
+        assert_eq!(m.trace(), 9);
     }
 
     #[test]
@@ -961,6 +1353,32 @@ mod tests {
         assert_eq!(m4.determinant(), -511916);
     }
 
+    #[test]
+    fn determinant_elimination_test() {
+        let m1 = matrix![[0, 2, 1], [3, 1, 4], [1, 0, 2]];
+        let m2 = matrix![[1, 2, 3], [4, 5, 6], [7, 8, 9]];
+        let m3 = matrix![[2., 1., 1.], [4., -6., 0.], [-2., 7., 2.]];
+        let m4: Matrix<i64> = matrix![
+            [2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1],
+            [0, 2, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0],
+            [0, 0, 2, 0, 0, 0, 0, 0, 0, 1, 0, 0],
+            [0, 0, 0, 2, 0, 0, 0, 0, 1, 0, 0, 0],
+            [0, 0, 0, 0, 2, 0, 0, 1, 0, 0, 0, 0],
+            [0, 0, 0, 0, 0, 2, 1, 0, 0, 0, 0, 0],
+            [0, 0, 0, 0, 0, 1, 2, 0, 0, 0, 0, 0],
+            [0, 0, 0, 0, 1, 0, 0, 2, 0, 0, 0, 0],
+            [0, 0, 0, 1, 0, 0, 0, 0, 2, 0, 0, 0],
+            [0, 0, 1, 0, 0, 0, 0, 0, 0, 2, 0, 0],
+            [0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 2, 0],
+            [1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2]
+        ];
+
+        assert_eq!(m1.determinant(), -5);
+        assert_eq!(m2.determinant(), 0);
+        assert_eq!(m3.determinant(), -16.);
+        assert_eq!(m4.determinant(), 729);
+    }
+
     #[test]
     fn bind_cols_test() {
         let mut m1 = matrix![[1, 2, 3], [4, 5, 6]];
@@ -988,21 +1406,140 @@ mod tests {
 
         let inv = m.inverse().unwrap();
 
-        assert!(
-            inv >= matrix![
-                [0.648, 0.096, -0.656],
-                [-0.782, -0.127, 0.964],
-                [0.142, 0.073, -0.207]
-            ]
+        // adj(m) / det(m)
+        let mut expected = matrix![[-113., -17., 114.], [136., 22., -168.], [-25., -13., 36.]];
+        expected.scl(-1. / 174.);
+
+        assert_matrix_approx_eq!(inv, expected, 1e-12);
+        assert_matrix_approx_eq!(m.mul_mat(&inv), m.identity(), 1e-12);
+    }
+
+    #[test]
+    fn pow_test() {
+        // Fibonacci: [[1, 1], [1, 0]]^n = [[F(n+1), F(n)], [F(n), F(n-1)]]
+        let fib = matrix![[1i64, 1], [1, 0]];
+        assert_eq!(fib.pow(0), matrix![[1, 0], [0, 1]]);
+        assert_eq!(fib.pow(1), fib);
+        assert_eq!(fib.pow(10), matrix![[89, 55], [55, 34]]);
+
+        // Closed walks of length 3 in a triangle
+        let triangle = matrix![[0, 1, 1], [1, 0, 1], [1, 1, 0]];
+        assert_eq!(triangle.pow(3), matrix![[2, 3, 3], [3, 2, 3], [3, 3, 2]]);
+
+        let m = matrix![[8., 5., -2.], [4., 7., 20.], [7., 6., 1.]];
+        let inv = m.inverse().unwrap();
+        assert_matrix_approx_eq!(m.pow(-2), inv.mul_mat(&inv), 1e-12);
+        assert_matrix_approx_eq!(m.pow(-3).mul_mat(&m.pow(3)), m.identity(), 1e-9);
+
+        assert!(matches!(
+            matrix![[1, 2, 3], [4, 5, 6]].try_pow(2),
+            Err(MatrixError::NotSquare {
+                operation: "pow",
+                ..
+            })
+        ));
+        assert_eq!(
+            matrix![[1., 2.], [2., 4.]].try_pow(-1),
+            Err(MatrixError::NotInvertible)
         );
 
-        assert!(
-            inv <= matrix![
-                [0.650, 0.099, -0.654],
-                [-0.780, -0.125, 0.967],
-                [0.15, 0.08, -0.205]
-            ]
+        // Integer inverses are exact, and exist only for det = +-1
+        let u = matrix![[2i64, 1], [1, 1]];
+        assert_eq!(u.pow(-1), matrix![[1, -1], [-1, 2]]);
+        assert_eq!(u.pow(-3).mul_mat(&u.pow(3)), u.identity());
+        let u3 = matrix![[2i64, 3, 1], [1, 2, 1], [1, 1, 1]];
+        assert_eq!(u3.pow(-1).mul_mat(&u3), u3.identity());
+        assert_eq!(matrix![[-1i64]].pow(-1), matrix![[-1]]);
+        assert_eq!(
+            matrix![[2i64, 0], [0, 2]].try_pow(-1),
+            Err(MatrixError::NotInvertible)
         );
+        assert_eq!(
+            matrix![[1i64, 2], [2, 4]].inverse(),
+            Err(MatrixError::NotInvertible)
+        );
+    }
+
+    #[test]
+    fn checked_pow_test() {
+        let fib = matrix![[1i64, 1], [1, 0]];
+
+        // F(92) is the largest Fibonacci number that fits in an i64
+        let f91 = fib.checked_pow(91).unwrap();
+        assert_eq!(f91[(0, 0)], 7540113804746346429);
+        assert_eq!(
+            fib.checked_pow(92),
+            Err(MatrixError::Overflow {
+                operation: "checked_pow"
+            })
+        );
+        assert_eq!(fib.checked_pow(0), Ok(fib.identity()));
+
+        assert_eq!(
+            matrix![[i8::MAX, 0], [0, 1]].checked_mul_mat(&matrix![[2, 0], [0, 1]]),
+            Err(MatrixError::Overflow {
+                operation: "checked_mul_mat"
+            })
+        );
+        assert_eq!(
+            matrix![[3i8, 0], [0, 1]].checked_mul_mat(&matrix![[2, 0], [0, 1]]),
+            Ok(matrix![[6, 0], [0, 1]])
+        );
+        assert!(matches!(
+            fib.checked_mul_mat(&matrix![[1i64, 2, 3]]),
+            Err(MatrixError::Incompatible { .. })
+        ));
+        assert!(matches!(
+            matrix![[1i64, 2]].checked_pow(2),
+            Err(MatrixError::NotSquare { .. })
+        ));
+    }
+
+    #[test]
+    fn solve_test() {
+        let m1 = matrix![[2., 1., 1.], [4., -6., 0.], [-2., 7., 2.]];
+        let m2 = matrix![[1., 2.], [2., 4.]];
+        let m3 = matrix![[1., 1.], [1., -1.], [2., 0.]];
+
+        assert_eq!(
+            m1.solve(&vector![5., -2., 9.]).unwrap(),
+            vector![1., 1., 2.]
+        );
+        assert!(matches!(
+            m2.solve(&vector![3., 6.]),
+            Err(MatrixError::Singular)
+        ));
+        assert!(matches!(
+            m2.solve(&vector![3., 5.]),
+            Err(MatrixError::Inconsistent)
+        ));
+        assert!(matches!(
+            m2.solve(&vector![3., 5., 1.]),
+            Err(MatrixError::Incompatible { .. })
+        ));
+        assert_eq!(m3.solve(&vector![3., 1., 4.]).unwrap(), vector![2., 1.]);
+        assert!(matches!(
+            m3.solve(&vector![3., 1., 5.]),
+            Err(MatrixError::Inconsistent)
+        ));
+    }
+
+    #[test]
+    fn solve_many_test() {
+        let m1 = matrix![[2., 1., 1.], [4., -6., 0.], [-2., 7., 2.]];
+        let m2 = matrix![[1., 2.], [2., 4.]];
+        let b1 = matrix![[5., 2.], [-2., 4.], [9., -2.]];
+        let b2 = matrix![[3., 3.], [6., 5.]];
+
+        assert_eq!(
+            m1.solve_many(&b1).unwrap(),
+            matrix![[1., 1.], [1., 0.], [2., 0.]]
+        );
+        assert!(matches!(m2.solve_many(&b2), Err(MatrixError::Inconsistent)));
+        assert!(matches!(
+            m1.solve_many(&b2),
+            Err(MatrixError::Incompatible { .. })
+        ));
     }
 
     #[test]
@@ -1014,5 +1551,139 @@ mod tests {
         assert_eq!(m1.rank(), 3);
         assert_eq!(m2.rank(), 2);
         assert_eq!(m3.rank(), 3);
+
+        // Pivot-free columns are skipped rather than ending elimination
+        let m4 = matrix![[0., 1., 0.], [0., 0., 1.], [0., 0., 0.]];
+        assert_eq!(m4.rank(), 2);
+
+        // Integer entries go through fraction-free steps
+        assert_eq!(matrix![[2, 3], [4, 5]].rank(), 2);
+        assert_eq!(matrix![[2, 4, 1], [1, 2, 3], [3, 6, 4]].rank(), 2);
+    }
+
+    #[test]
+    fn try_ops_test() {
+        let sq = matrix![[1, 2], [3, 4]];
+        let wide = matrix![[1, 2, 3], [4, 5, 6]];
+        let shape = |rows, cols| Dimension::D2(D2 { rows, cols });
+
+        assert_eq!(
+            sq.try_mul_mat(&wide),
+            Ok(matrix![[9, 12, 15], [19, 26, 33]])
+        );
+        assert_eq!(
+            wide.try_mul_mat(&sq),
+            Err(MatrixError::Incompatible {
+                operation: "mul_mat",
+                expected: shape(3, 2),
+                actual: shape(2, 2),
+            })
+        );
+        assert_eq!(sq.try_mul_vec(&vector![1, 1]), Ok(vector![3, 7]));
+        assert!(sq.try_mul_vec(&vector![1, 1, 1]).is_err());
+
+        assert_eq!(sq.try_trace(), Ok(5));
+        assert_eq!(sq.try_determinant(), Ok(-2));
+        assert_eq!(
+            wide.try_determinant(),
+            Err(MatrixError::NotSquare {
+                operation: "determinant",
+                shape: shape(2, 3),
+            })
+        );
+        assert!(wide.try_trace().is_err());
+        assert!(wide.try_identity().is_err());
+        assert!(matrix![[1., 2.], [3., 4.], [5., 6.]].inverse().is_err());
+        assert!(wide.try_lu().is_err());
+        assert!(matrix![[1., 2., 3.], [4., 5., 6.]]
+            .try_hessenberg()
+            .is_err());
+
+        let mut m = sq.clone();
+        assert!(m.try_add(&wide).is_err());
+        assert_eq!(m, sq);
+        assert!(m.try_sub(&sq).is_ok());
+        assert_eq!(m, matrix![[0, 0], [0, 0]]);
+
+        assert!(m.try_append_col(vector![1, 2, 3]).is_err());
+        assert!(m.try_append_col(vector![1, 2]).is_ok());
+        assert!(m.try_bind_cols(matrix![[1], [2], [3]]).is_err());
+        assert_eq!(m, matrix![[0, 0, 1], [0, 0, 2]]);
+    }
+
+    #[test]
+    fn try_from_test() {
+        assert_eq!(
+            Matrix::try_from([1, 2, 3].as_slice()),
+            Err(MatrixError::NotSquare {
+                operation: "from",
+                shape: Dimension::D1(D1 { length: 3 }),
+            })
+        );
+        assert_eq!(
+            Matrix::try_from(&[] as &[i32]),
+            Err(MatrixError::Empty { operation: "from" })
+        );
+        assert_eq!(
+            Matrix::<i32>::try_from([vector![1, 2], vector![3]].as_slice()),
+            Err(MatrixError::NotRectangular { operation: "from" })
+        );
+        assert_eq!(
+            Matrix::try_from_cols(vector![vector![1, 2], vector![3]]),
+            Err(MatrixError::NotRectangular { operation: "from" })
+        );
+
+        let e = Matrix::try_from([1, 2, 3].as_slice()).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "from: expected a square matrix, received shape 3"
+        );
+    }
+
+    #[test]
+    fn tolerance_test() {
+        // Singular, but elimination leaves rounding noise in the last row
+        let m: Matrix<f64> = matrix![[0.1, 0.2, 0.3], [0.4, 0.5, 0.6], [0.7, 0.8, 0.9]];
+
+        assert!(m.default_tolerance() > 0. && m.default_tolerance() < 1e-14);
+        assert_eq!(m.rank(), 2);
+        assert_eq!(m.rank_with_tol(0.), 3);
+        let r = m.row_echelon();
+        assert!((0..3).all(|j| r[(2, j)] == 0.));
+        assert!(m.inverse().is_err());
+
+        let near: Matrix<f64> = matrix![[1., 1.], [1., 1. + 1e-9]];
+
+        assert_eq!(near.rank(), 2);
+        assert_eq!(near.rank_with_tol(1e-6), 1);
+        assert!(near.inverse().is_ok());
+        assert!(near.inverse_with_tol(1e-6).is_err());
+        assert_eq!(near.row_echelon_with_tol(1e-6), matrix![[1., 1.], [0., 0.]]);
+
+        // The same matrix scaled up: a relative tolerance follows the entries
+        let mut big = near.clone();
+        big.scl(1e9);
+        assert_eq!(big.rank_with_tol(1e-6), 2);
+        assert!(big.inverse_with_tol(Tolerance::relative(1e-6)).is_err());
+        assert_eq!(big.rank_with_tol(Tolerance::relative(1e-6)), 1);
+        assert_eq!(big.rank_with_tol(Tolerance::new(2., 1e-12)), 1);
+        assert_eq!(
+            big.row_echelon_with_tol(Tolerance::new(2., 1e-12)),
+            matrix![[1., 1.], [0., 0.]]
+        );
+        assert_eq!(Tolerance::new(2., 1e-12).threshold(&big), 2.);
+        assert!((Tolerance::relative(1e-6).threshold(&big) - 1e3).abs() < 1e-5);
+
+        // LU and solve share the default tolerance
+        assert!(m.lu().is_singular());
+        assert!(!m.lu_with_tol(0.).is_singular());
+        assert!(near.lu_with_tol(1e-6).is_singular());
+        assert_eq!(m.solve(&vector![0.6, 1.5, 2.4]), Err(MatrixError::Singular));
+        assert_eq!(
+            m.solve(&vector![1., 1., 0.]),
+            Err(MatrixError::Inconsistent)
+        );
+
+        assert_eq!(matrix![[1, 2], [3, 4]].default_tolerance(), 0);
     }
 }
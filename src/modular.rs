@@ -0,0 +1,400 @@
+use super::FieldBound;
+
+use std::fmt;
+use std::ops;
+
+// Element of the prime field GF(P), stored as its representative in 0..P.
+// P must be prime, otherwise division is not well defined; any other P is
+// rejected when the type is first used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Modular<const P: u64> {
+    value: u64,
+}
+
+// Deterministic Miller-Rabin, these bases cover every u64
+const fn is_prime(n: u64) -> bool {
+    const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+    if n < 2 {
+        return false;
+    }
+    let mut i = 0;
+    while i < BASES.len() {
+        if n.is_multiple_of(BASES[i]) {
+            return n == BASES[i];
+        }
+        i += 1;
+    }
+
+    let (mut d, mut s) = (n - 1, 0);
+    while d % 2 == 0 {
+        d /= 2;
+        s += 1;
+    }
+
+    let mut i = 0;
+    'bases: while i < BASES.len() {
+        let mut x = pow_mod(BASES[i], d, n);
+        i += 1;
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        let mut r = 1;
+        while r < s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                continue 'bases;
+            }
+            r += 1;
+        }
+        return false;
+    }
+
+    true
+}
+
+const fn mul_mod(a: u64, b: u64, n: u64) -> u64 {
+    ((a as u128 * b as u128) % n as u128) as u64
+}
+
+const fn pow_mod(mut base: u64, mut exp: u64, n: u64) -> u64 {
+    let mut acc = 1;
+    base %= n;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            acc = mul_mod(acc, base, n);
+        }
+        base = mul_mod(base, base, n);
+        exp >>= 1;
+    }
+    acc
+}
+
+impl<const P: u64> Modular<P> {
+    const PRIME_MODULUS: () = assert!(is_prime(P), "Modular<P> requires a prime modulus");
+
+    pub fn new(value: u64) -> Self {
+        let () = Self::PRIME_MODULUS;
+        Modular { value: value % P }
+    }
+
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    pub fn pow(&self, mut exp: u64) -> Self {
+        let mut base = *self;
+        let mut acc = Self::ONE;
+
+        while exp > 0 {
+            if exp & 1 == 1 {
+                acc = acc * base;
+            }
+            base = base * base;
+            exp >>= 1;
+        }
+
+        acc
+    }
+
+    // Multiplicative inverse by Fermat's little theorem, None for zero
+    pub fn inverse(&self) -> Option<Self> {
+        if self.value == 0 {
+            None
+        } else {
+            Some(self.pow(P - 2))
+        }
+    }
+
+    // Square root by Tonelli-Shanks, the smaller of the two roots
+    pub fn checked_sqrt(&self) -> Option<Self> {
+        if self.value == 0 || P == 2 {
+            return Some(*self);
+        }
+        if self.pow((P - 1) / 2) != Self::ONE {
+            return None;
+        }
+
+        let (mut q, mut s) = (P - 1, 0);
+        while q % 2 == 0 {
+            q /= 2;
+            s += 1;
+        }
+
+        let mut z = Modular::new(2);
+        while z.pow((P - 1) / 2) == Self::ONE {
+            z = z + Self::ONE;
+        }
+
+        let mut c = z.pow(q);
+        let mut t = self.pow(q);
+        let mut r = self.pow(q.div_ceil(2));
+        let mut m = s;
+
+        while t != Self::ONE {
+            let mut i = 0;
+            let mut t2 = t;
+            while t2 != Self::ONE {
+                t2 = t2 * t2;
+                i += 1;
+            }
+
+            let b = c.pow(1 << (m - i - 1));
+            r = r * b;
+            c = b * b;
+            t = t * c;
+            m = i;
+        }
+
+        Some(if r.value > P - r.value { -r } else { r })
+    }
+}
+
+impl<const P: u64> From<u64> for Modular<P> {
+    fn from(value: u64) -> Self {
+        Modular::new(value)
+    }
+}
+
+impl<const P: u64> From<i64> for Modular<P> {
+    fn from(value: i64) -> Self {
+        let () = Self::PRIME_MODULUS;
+        Modular {
+            value: (value as i128).rem_euclid(P as i128) as u64,
+        }
+    }
+}
+
+impl<const P: u64> fmt::Display for Modular<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+impl<const P: u64> ops::Neg for Modular<P> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        if self.value == 0 {
+            self
+        } else {
+            Modular {
+                value: P - self.value,
+            }
+        }
+    }
+}
+
+impl<const P: u64> ops::Add for Modular<P> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        // Written to stay below P, so it cannot overflow for large moduli
+        let gap = P - rhs.value;
+        let value = if self.value >= gap {
+            self.value - gap
+        } else {
+            self.value + rhs.value
+        };
+        Modular { value }
+    }
+}
+
+impl<const P: u64> ops::Sub for Modular<P> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+impl<const P: u64> ops::Mul for Modular<P> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Modular {
+            value: ((self.value as u128 * rhs.value as u128) % P as u128) as u64,
+        }
+    }
+}
+
+impl<const P: u64> ops::Div for Modular<P> {
+    type Output = Self;
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        match rhs.inverse() {
+            Some(inv) => self * inv,
+            None => panic!("attempt to divide by zero in GF({})", P),
+        }
+    }
+}
+
+macro_rules! impl_ops_assign {
+    ($trait:ty, $fun:ident, $op:tt) => {
+        impl<const P: u64> $trait for Modular<P> {
+            fn $fun(&mut self, rhs: &Self) {
+                *self = *self $op *rhs;
+            }
+        }
+    };
+}
+
+impl_ops_assign!(ops::AddAssign<&Self>, add_assign, +);
+impl_ops_assign!(ops::SubAssign<&Self>, sub_assign, -);
+impl_ops_assign!(ops::MulAssign<&Self>, mul_assign, *);
+impl_ops_assign!(ops::DivAssign<&Self>, div_assign, /);
+
+impl<const P: u64> FieldBound for Modular<P> {
+    const ZERO: Self = {
+        let () = Self::PRIME_MODULUS;
+        Modular { value: 0 }
+    };
+    const ONE: Self = {
+        let () = Self::PRIME_MODULUS;
+        Modular { value: 1 }
+    };
+
+    // No absolute value in GF(P); every nonzero element is a valid pivot
+    fn abs(&self) -> Self {
+        *self
+    }
+
+    fn sqrt(&self) -> Self {
+        match self.checked_sqrt() {
+            Some(r) => r,
+            None => panic!("{} is not a square in GF({})", self, P),
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.value == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::matrix::matrix;
+    use crate::matrix::Matrix;
+    use crate::vector::vector;
+    use crate::MatrixError;
+
+    type F7 = Modular<7>;
+
+    fn f7(v: i64) -> F7 {
+        Modular::from(v)
+    }
+
+    fn f5(v: i64) -> Modular<5> {
+        Modular::from(v)
+    }
+
+    #[test]
+    fn arithmetic_test() {
+        assert_eq!(f7(-1).value(), 6);
+        assert_eq!(f7(5) + f7(4), f7(2));
+        assert_eq!(f7(2) - f7(5), f7(4));
+        assert_eq!(f7(3) * f7(5), f7(1));
+        assert_eq!(f7(1) / f7(3), f7(5));
+        assert_eq!(f7(3).inverse(), Some(f7(5)));
+        assert_eq!(f7(0).inverse(), None);
+        assert_eq!(f7(3).pow(6), f7(1));
+
+        // Near the top of u64, sums and products must not overflow
+        type Big = Modular<18446744073709551557>;
+        let a = Big::new(u64::MAX - 60);
+        assert_eq!(a + a, Big::new(18446744073709551553));
+        assert_eq!(a * a, Big::new(4));
+    }
+
+    #[test]
+    fn is_prime_test() {
+        let small: Vec<u64> = (0..30).filter(|&n| is_prime(n)).collect();
+        assert_eq!(small, vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+
+        assert!(is_prime(18446744073709551557));
+        assert!(!is_prime(u64::MAX));
+        // Strong pseudoprime to bases 2 through 11
+        assert!(!is_prime(2152302898747));
+        assert!(!is_prime(4294967291 * 4294967279));
+    }
+
+    #[test]
+    #[should_panic]
+    fn division_by_zero_test() {
+        let _ = f7(3) / f7(0);
+    }
+
+    #[test]
+    fn sqrt_test() {
+        assert_eq!(f7(2).sqrt(), f7(3));
+        assert_eq!(f7(3).checked_sqrt(), None);
+        assert_eq!(Modular::<13>::new(10).sqrt(), Modular::new(6));
+
+        // 41 = 1 + 5 * 2^3 exercises the Tonelli-Shanks loop
+        for v in 1..41 {
+            if let Some(r) = Modular::<41>::new(v).checked_sqrt() {
+                assert_eq!(r * r, Modular::new(v));
+            }
+        }
+    }
+
+    #[test]
+    fn determinant_test() {
+        // det = -2
+        assert_eq!(matrix![[f7(1), f7(2)], [f7(3), f7(4)]].determinant(), f7(5));
+        assert_eq!(matrix![[f5(1), f5(2)], [f5(3), f5(1)]].determinant(), f5(0));
+
+        // det = 6 over the integers
+        let m = matrix![
+            [f7(2), f7(0), f7(1)],
+            [f7(1), f7(3), f7(2)],
+            [f7(1), f7(1), f7(2)]
+        ];
+        assert_eq!(m.determinant(), f7(6));
+    }
+
+    #[test]
+    fn inverse_test() {
+        let m = matrix![[f7(1), f7(2)], [f7(3), f7(4)]];
+        let inv = m.inverse().unwrap();
+
+        // -1/2 [[4, -2], [-3, 1]] with 1/2 = 4
+        assert_eq!(inv, matrix![[f7(5), f7(1)], [f7(5), f7(3)]]);
+        assert_eq!(m.mul_mat(&inv), m.identity());
+        assert!(matrix![[f5(1), f5(2)], [f5(3), f5(1)]].inverse().is_err());
+    }
+
+    #[test]
+    fn rank_test() {
+        // Invertible over the rationals, singular mod 5
+        assert_eq!(matrix![[f5(1), f5(2)], [f5(3), f5(1)]].rank(), 1);
+        assert_eq!(matrix![[f7(1), f7(2)], [f7(3), f7(1)]].rank(), 2);
+
+        let m: Matrix<Modular<2>> = matrix![
+            [Modular::new(1), Modular::new(1), Modular::new(0)],
+            [Modular::new(0), Modular::new(1), Modular::new(1)],
+            [Modular::new(1), Modular::new(0), Modular::new(1)]
+        ];
+        assert_eq!(m.rank(), 2);
+    }
+
+    #[test]
+    fn row_echelon_test() {
+        let m = matrix![
+            [f5(1), f5(2), f5(3)],
+            [f5(3), f5(1), f5(4)],
+            [f5(2), f5(4), f5(2)]
+        ];
+
+        assert_eq!(
+            m.row_echelon(),
+            matrix![
+                [f5(1), f5(2), f5(0)],
+                [f5(0), f5(0), f5(1)],
+                [f5(0), f5(0), f5(0)]
+            ]
+        );
+        assert!(matches!(
+            m.solve(&vector![f5(1), f5(3), f5(2)]),
+            Err(MatrixError::Singular)
+        ));
+    }
+}
@@ -0,0 +1,473 @@
+use super::Dimension;
+use super::FieldBound;
+use super::FloatBound;
+use super::MatrixError;
+use super::D2;
+
+use super::matrix::Matrix;
+use super::sparse::CsrMatrix;
+use super::vector::Vector;
+
+// Approximation M of A that is cheap to invert. apply returns z = M^{-1} r,
+// the solvers call it once or twice per iteration.
+pub trait Preconditioner<K: FieldBound> {
+    fn apply(&self, r: &Vector<K>) -> Vector<K>;
+}
+
+// M = I, what the unpreconditioned solvers use
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IdentityPreconditioner;
+
+impl<K: FieldBound> Preconditioner<K> for IdentityPreconditioner {
+    fn apply(&self, r: &Vector<K>) -> Vector<K> {
+        r.clone()
+    }
+}
+
+// M = diag(A)
+#[derive(Debug, Clone, PartialEq)]
+pub struct JacobiPreconditioner<K: FloatBound> {
+    inv_diag: Vec<K>,
+}
+
+// A ~ L U with L unit lower and U upper triangular, both restricted to the
+// sparsity pattern of A. Stored together in one CSR matrix, without the
+// entries that cancelled to zero.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ilu0<K: FloatBound> {
+    lu: CsrMatrix<K>,
+    diag: Vec<usize>, // position of u_ii in lu.values()
+}
+
+// A ~ L L^T with L restricted to the lower triangular pattern of A
+#[derive(Debug, Clone, PartialEq)]
+pub struct IncompleteCholesky<K: FloatBound> {
+    l: CsrMatrix<K>, // lower triangle only, diagonal last in every row
+}
+
+fn check_square<K: FloatBound>(
+    operation: &'static str,
+    a: &CsrMatrix<K>,
+) -> Result<(), MatrixError> {
+    if a.n_rows() != a.n_cols() {
+        return Err(MatrixError::NotSquare {
+            operation,
+            shape: Dimension::D2(D2 {
+                rows: a.n_rows(),
+                cols: a.n_cols(),
+            }),
+        });
+    }
+    Ok(())
+}
+
+// Position of the diagonal entry of every row
+fn diagonal_positions<K: FloatBound>(a: &CsrMatrix<K>) -> Result<Vec<usize>, MatrixError> {
+    let mut diag = Vec::with_capacity(a.n_rows());
+    for i in 0..a.n_rows() {
+        let start = a.indptr()[i];
+        match a.row(i).0.binary_search(&i) {
+            Ok(p) => diag.push(start + p),
+            Err(_) => return Err(MatrixError::ZeroPivot { order: i + 1 }),
+        }
+    }
+    Ok(diag)
+}
+
+impl<K: FloatBound> JacobiPreconditioner<K> {
+    pub fn new(a: &Matrix<K>) -> Result<Self, MatrixError> {
+        Self::from_csr(&CsrMatrix::from(a))
+    }
+
+    pub fn from_csr(a: &CsrMatrix<K>) -> Result<Self, MatrixError> {
+        check_square("jacobi_preconditioner", a)?;
+
+        let diag = diagonal_positions(a)?;
+        Ok(JacobiPreconditioner {
+            inv_diag: diag.iter().map(|&p| K::ONE / a.values()[p]).collect(),
+        })
+    }
+}
+
+impl<K: FloatBound> Preconditioner<K> for JacobiPreconditioner<K> {
+    fn apply(&self, r: &Vector<K>) -> Vector<K> {
+        let mut z = r.clone();
+        for (z_i, d) in (&mut z).into_iter().zip(self.inv_diag.iter()) {
+            *z_i *= d;
+        }
+        z
+    }
+}
+
+impl<K: FloatBound> Ilu0<K> {
+    pub fn new(a: &Matrix<K>) -> Result<Self, MatrixError> {
+        Self::from_csr(&CsrMatrix::from(a))
+    }
+
+    pub fn from_csr(a: &CsrMatrix<K>) -> Result<Self, MatrixError> {
+        check_square("ilu0", a)?;
+
+        let n = a.n_rows();
+        let diag = diagonal_positions(a)?;
+        let indptr = a.indptr();
+        let indices = a.indices();
+        let mut values = a.values().to_vec();
+
+        // IKJ elimination; position[j] maps column j to its slot in row i
+        let mut position = vec![usize::MAX; n];
+        for i in 0..n {
+            let (start, end) = (indptr[i], indptr[i + 1]);
+            for p in start..end {
+                position[indices[p]] = p;
+            }
+
+            for p in start..diag[i] {
+                let k = indices[p];
+                let pivot = values[diag[k]];
+                if pivot.is_zero() {
+                    return Err(MatrixError::ZeroPivot { order: k + 1 });
+                }
+                values[p] /= &pivot;
+
+                let l_ik = values[p];
+                for q in diag[k] + 1..indptr[k + 1] {
+                    let slot = position[indices[q]];
+                    if slot != usize::MAX {
+                        let u_kj = values[q];
+                        values[slot] -= &(l_ik * u_kj);
+                    }
+                }
+            }
+
+            if values[diag[i]].is_zero() {
+                return Err(MatrixError::ZeroPivot { order: i + 1 });
+            }
+            for p in start..end {
+                position[indices[p]] = usize::MAX;
+            }
+        }
+
+        // Drop entries that cancelled, the pivots are known to be nonzero
+        let mut lu_indptr = vec![0; n + 1];
+        let mut lu_indices = Vec::with_capacity(indices.len());
+        let mut lu_values = Vec::with_capacity(values.len());
+        for i in 0..n {
+            for p in indptr[i]..indptr[i + 1] {
+                if !values[p].is_zero() {
+                    lu_indices.push(indices[p]);
+                    lu_values.push(values[p]);
+                }
+            }
+            lu_indptr[i + 1] = lu_indices.len();
+        }
+
+        let lu = CsrMatrix::from_raw_parts(n, n, lu_indptr, lu_indices, lu_values);
+        let diag = diagonal_positions(&lu)?;
+        Ok(Ilu0 { lu, diag })
+    }
+}
+
+impl<K: FloatBound> Preconditioner<K> for Ilu0<K> {
+    fn apply(&self, r: &Vector<K>) -> Vector<K> {
+        let n = r.len();
+        let values = self.lu.values();
+        let mut z = r.clone();
+
+        // L y = r, unit diagonal
+        for i in 0..n {
+            let (cols, _) = self.lu.row(i);
+            let start = self.lu.indptr()[i];
+            for (p, &j) in (start..self.diag[i]).zip(cols) {
+                let y_j = z[j];
+                z[i] -= &(values[p] * y_j);
+            }
+        }
+
+        // U z = y
+        for i in (0..n).rev() {
+            let end = self.lu.indptr()[i + 1];
+            let indices = self.lu.indices();
+            for p in self.diag[i] + 1..end {
+                let z_j = z[indices[p]];
+                z[i] -= &(values[p] * z_j);
+            }
+            z[i] /= &values[self.diag[i]];
+        }
+
+        z
+    }
+}
+
+impl<K: FloatBound> IncompleteCholesky<K> {
+    pub fn new(a: &Matrix<K>) -> Result<Self, MatrixError> {
+        a.check_symmetric("incomplete_cholesky")?;
+        Self::from_csr(&CsrMatrix::from(a))
+    }
+
+    // Only the lower triangle of a is read, a is assumed to be symmetric
+    pub fn from_csr(a: &CsrMatrix<K>) -> Result<Self, MatrixError> {
+        check_square("incomplete_cholesky", a)?;
+        diagonal_positions(a)?;
+
+        let n = a.n_rows();
+        let mut indptr = vec![0; n + 1];
+        let mut indices = Vec::new();
+        let mut values: Vec<K> = Vec::new();
+
+        // Row i of L against the earlier rows; row i is scattered into `dense`
+        let mut dense = vec![K::ZERO; n];
+        for i in 0..n {
+            let (cols, vals) = a.row(i);
+            let row_start = indices.len();
+
+            for (&k, a_ik) in cols.iter().zip(vals).take_while(|(&k, _)| k <= i) {
+                let mut s = *a_ik;
+
+                if k < i {
+                    // l_ik = (a_ik - sum_{j < k} l_ij l_kj) / l_kk over row k of L
+                    let diag_k = indptr[k + 1] - 1;
+                    for p in indptr[k]..diag_k {
+                        s -= &(dense[indices[p]] * values[p]);
+                    }
+                    dense[k] = s / values[diag_k];
+                    indices.push(k);
+                    values.push(dense[k]);
+                } else {
+                    // l_ii = sqrt(a_ii - sum_{j < i} l_ij^2)
+                    for l_ij in &values[row_start..] {
+                        s -= &(*l_ij * *l_ij);
+                    }
+                    if s <= K::ZERO {
+                        return Err(MatrixError::NotPositiveDefinite { order: i + 1 });
+                    }
+                    indices.push(i);
+                    values.push(s.sqrt());
+                }
+            }
+
+            for &j in &indices[row_start..] {
+                dense[j] = K::ZERO;
+            }
+            indptr[i + 1] = indices.len();
+        }
+
+        Ok(IncompleteCholesky {
+            l: CsrMatrix::from_raw_parts(n, n, indptr, indices, values),
+        })
+    }
+}
+
+impl<K: FloatBound> Preconditioner<K> for IncompleteCholesky<K> {
+    fn apply(&self, r: &Vector<K>) -> Vector<K> {
+        let n = r.len();
+        let mut z = r.clone();
+
+        // L y = r
+        for i in 0..n {
+            let (cols, vals) = self.l.row(i);
+            let last = cols.len() - 1;
+            for (&j, l_ij) in cols[..last].iter().zip(vals) {
+                let y_j = z[j];
+                z[i] -= &(*l_ij * y_j);
+            }
+            z[i] /= &vals[last];
+        }
+
+        // L^T z = y, walking the rows of L as columns of L^T
+        for i in (0..n).rev() {
+            let (cols, vals) = self.l.row(i);
+            let last = cols.len() - 1;
+            z[i] /= &vals[last];
+            let z_i = z[i];
+            for (&j, l_ij) in cols[..last].iter().zip(vals) {
+                z[j] -= &(*l_ij * z_i);
+            }
+        }
+
+        z
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::approx::assert_vector_approx_eq;
+    use crate::iterative::{
+        bicgstab_preconditioned, bicgstab_with, cg, cg_preconditioned, gmres_preconditioned,
+        gmres_with,
+    };
+    use crate::matrix::matrix;
+    use crate::sparse::CooMatrix;
+    use crate::vector::vector;
+
+    // 5-point Laplacian on an m x m grid, n = m^2 unknowns
+    fn poisson_2d(m: usize) -> CsrMatrix<f64> {
+        let mut coo = CooMatrix::new(m * m, m * m);
+        for i in 0..m {
+            for j in 0..m {
+                let p = i * m + j;
+                coo.push(p, p, 4.);
+                if i > 0 {
+                    coo.push(p, p - m, -1.);
+                }
+                if i + 1 < m {
+                    coo.push(p, p + m, -1.);
+                }
+                if j > 0 {
+                    coo.push(p, p - 1, -1.);
+                }
+                if j + 1 < m {
+                    coo.push(p, p + 1, -1.);
+                }
+            }
+        }
+        coo.to_csr()
+    }
+
+    #[test]
+    fn exact_factorization_test() {
+        // Tridiagonal matrices have no fill-in, so ILU(0) and IC(0) are exact
+        let a = matrix![[4., -1., 0.], [-1., 4., -1.], [0., -1., 4.]];
+        let b = vector![2., 4., 10.];
+        let x = a.solve(&b).unwrap();
+
+        assert_vector_approx_eq!(Ilu0::new(&a).unwrap().apply(&b), x, 1e-12);
+        assert_vector_approx_eq!(IncompleteCholesky::new(&a).unwrap().apply(&b), x, 1e-12);
+
+        let d = JacobiPreconditioner::new(&a).unwrap();
+        assert_eq!(d.apply(&b), vector![0.5, 1., 2.5]);
+        assert_eq!(IdentityPreconditioner.apply(&b), b);
+    }
+
+    #[test]
+    fn ilu0_pattern_test() {
+        // Fill-in at (2, 1) and (1, 2) is dropped
+        let a = matrix![[4., 1., 1.], [1., 4., 0.], [1., 0., 4.]];
+        let ilu = Ilu0::new(&a).unwrap();
+        assert_eq!(ilu.lu.nnz(), 7);
+        assert_eq!(ilu.lu.values(), &[4., 1., 1., 0.25, 3.75, 0.25, 3.75]);
+
+        let ic = IncompleteCholesky::new(&a).unwrap();
+        assert_eq!(
+            ic.l.values(),
+            &[2., 0.5, 3.75f64.sqrt(), 0.5, 3.75f64.sqrt()]
+        );
+
+        // u_12 = 1 - 1 * 1 cancels and is not stored
+        let a = matrix![[1., 1., 1.], [1., 2., 1.], [0., 1., 2.]];
+        let ilu = Ilu0::new(&a).unwrap();
+        assert_eq!(ilu.lu.nnz(), 7);
+        assert!(ilu.lu.values().iter().all(|v| *v != 0.));
+        assert_vector_approx_eq!(
+            ilu.apply(&vector![1., 2., 3.]),
+            a.solve(&vector![1., 2., 3.]).unwrap(),
+            1e-12
+        );
+    }
+
+    #[test]
+    fn preconditioner_errors_test() {
+        let zero_diag = matrix![[0., 1.], [1., 0.]];
+        assert_eq!(
+            JacobiPreconditioner::new(&zero_diag),
+            Err(MatrixError::ZeroPivot { order: 1 })
+        );
+        assert_eq!(
+            Ilu0::new(&zero_diag),
+            Err(MatrixError::ZeroPivot { order: 1 })
+        );
+        assert_eq!(
+            IncompleteCholesky::new(&matrix![[1., 2.], [2., 1.]]),
+            Err(MatrixError::NotPositiveDefinite { order: 2 })
+        );
+        assert_eq!(
+            IncompleteCholesky::new(&matrix![[1., 2.], [0., 1.]]),
+            Err(MatrixError::NotSymmetric)
+        );
+
+        let wide = matrix![[1., 2., 3.], [4., 5., 6.]];
+        assert!(matches!(
+            JacobiPreconditioner::new(&wide),
+            Err(MatrixError::NotSquare { .. })
+        ));
+        assert!(matches!(
+            Ilu0::new(&wide),
+            Err(MatrixError::NotSquare { .. })
+        ));
+        assert!(matches!(
+            IncompleteCholesky::from_csr(&CsrMatrix::from(&wide)),
+            Err(MatrixError::NotSquare { .. })
+        ));
+    }
+
+    #[test]
+    fn poisson_cg_test() {
+        let a = poisson_2d(16);
+        let b = vector![1.; 256];
+
+        let (x, plain) = cg(&a, &b).unwrap();
+        let ic = IncompleteCholesky::from_csr(&a).unwrap();
+        let (y, preconditioned) = cg_preconditioned(&a, &b, &ic, 1e-8, 1000).unwrap();
+
+        assert_vector_approx_eq!(x, y, 1e-6);
+        assert!(preconditioned.iterations() < plain.iterations() * 2 / 3);
+    }
+
+    #[test]
+    fn poisson_ilu_test() {
+        let a = poisson_2d(16);
+        let b = vector![1.; 256];
+        let ilu = Ilu0::from_csr(&a).unwrap();
+
+        let (x, plain) = gmres_with(&a, &b, 30, 1e-8, 1000).unwrap();
+        let (y, preconditioned) = gmres_preconditioned(&a, &b, &ilu, 30, 1e-8, 1000).unwrap();
+        assert_vector_approx_eq!(x, y, 1e-6);
+        assert!(preconditioned.iterations() < plain.iterations() * 2 / 3);
+
+        let (_, plain) = bicgstab_with(&a, &b, 1e-8, 1000).unwrap();
+        let (z, preconditioned) = bicgstab_preconditioned(&a, &b, &ilu, 1e-8, 1000).unwrap();
+        assert_vector_approx_eq!(x, z, 1e-6);
+        assert!(preconditioned.iterations() < plain.iterations() * 2 / 3);
+    }
+
+    #[test]
+    fn poisson_richardson_test() {
+        // The stationary solvers take any preconditioner too
+        let a = poisson_2d(10);
+        let dense = a.to_dense();
+        let b = vector![1.; 100];
+
+        let jacobi = JacobiPreconditioner::from_csr(&a).unwrap();
+        let (x, plain) = dense
+            .richardson_preconditioned(&b, &jacobi, 1., 1e-8, 2000)
+            .unwrap();
+        let ic = IncompleteCholesky::from_csr(&a).unwrap();
+        let (y, preconditioned) = dense
+            .richardson_preconditioned(&b, &ic, 1., 1e-8, 2000)
+            .unwrap();
+
+        assert_vector_approx_eq!(x, y, 1e-6);
+        assert!(preconditioned.iterations() < plain.iterations() / 2);
+    }
+
+    #[test]
+    fn poisson_jacobi_test() {
+        // Jacobi only helps when the diagonal varies: scale rows and columns
+        // of the Poisson matrix by s_i = 1 + i
+        let a = poisson_2d(10);
+        let mut coo = CooMatrix::new(100, 100);
+        for (i, j, k) in a.iter() {
+            coo.push(i, j, k * (1. + i as f64) * (1. + j as f64));
+        }
+        let scaled = coo.to_csr();
+        let b = vector![1.; 100];
+
+        let (x, plain) = cg(&scaled, &b).unwrap();
+        let jacobi = JacobiPreconditioner::from_csr(&scaled).unwrap();
+        let (y, preconditioned) = cg_preconditioned(&scaled, &b, &jacobi, 1e-8, 1000).unwrap();
+
+        assert_vector_approx_eq!(x, y, 1e-6);
+        assert!(preconditioned.iterations() < plain.iterations() / 2);
+    }
+}
@@ -0,0 +1,267 @@
+use super::FloatBound;
+use super::MatrixError;
+use super::VectorSpace;
+
+use super::matrix::matrix;
+use super::matrix::Matrix;
+use super::vector::vector;
+use super::vector::Vector;
+
+// Householder factorization A = QR of an m x n matrix.
+// Q is kept implicitly as the product of the reflectors H_k = I - 2 v_k v_k^T.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QR<K: FloatBound> {
+    r: Matrix<K>,
+    reflectors: Vec<Vector<K>>, // v_k acts on rows k..m
+}
+
+impl<K: FloatBound> Matrix<K> {
+    pub fn qr(&self) -> QR<K> {
+        let (m, n) = (self.n_rows(), self.n_cols());
+        let mut r = self.clone();
+        let mut reflectors = vec![];
+
+        for k in 0..n.min(m.saturating_sub(1)) {
+            let mut v = vector![K::ZERO; m - k];
+            for i in k..m {
+                v[i - k] = r[(i, k)];
+            }
+
+            let x_norm = v.norm();
+            let alpha = if v[0] >= K::ZERO { -x_norm } else { x_norm };
+            v[0] -= &alpha;
+
+            let v_norm = v.norm();
+            if v_norm.is_zero() {
+                reflectors.push(v);
+                continue;
+            }
+            v /= v_norm;
+
+            for j in k..n {
+                let mut d = K::ZERO;
+                for i in k..m {
+                    d += &(v[i - k] * r[(i, j)]);
+                }
+                for i in k..m {
+                    r[(i, j)] -= &(v[i - k] * d * K::from_f64(2.));
+                }
+            }
+            for i in (k + 1)..m {
+                r[(i, k)] = K::ZERO;
+            }
+
+            reflectors.push(v);
+        }
+
+        QR { r, reflectors }
+    }
+
+    pub fn least_squares(&self, b: &Vector<K>) -> Result<(Vector<K>, K), MatrixError> {
+        self.qr().least_squares(b)
+    }
+}
+
+impl<K: FloatBound> QR<K> {
+    fn n_rows(&self) -> usize {
+        self.r.n_rows()
+    }
+
+    fn n_cols(&self) -> usize {
+        self.r.n_cols()
+    }
+
+    fn reflect(v: &Vector<K>, offset: usize, x: &mut Vector<K>) {
+        let mut d = K::ZERO;
+        for i in 0..v.len() {
+            d += &(v[i] * x[offset + i]);
+        }
+        for i in 0..v.len() {
+            x[offset + i] -= &(v[i] * d * K::from_f64(2.));
+        }
+    }
+
+    // Q^T b
+    pub fn apply_qt(&self, b: &Vector<K>) -> Vector<K> {
+        let mut y = b.clone();
+        for (k, v) in self.reflectors.iter().enumerate() {
+            Self::reflect(v, k, &mut y);
+        }
+        y
+    }
+
+    // Q b
+    pub fn apply_q(&self, b: &Vector<K>) -> Vector<K> {
+        let mut y = b.clone();
+        for (k, v) in self.reflectors.iter().enumerate().rev() {
+            Self::reflect(v, k, &mut y);
+        }
+        y
+    }
+
+    fn q_cols(&self, cols: usize) -> Matrix<K> {
+        let m = self.n_rows();
+        let mut q = matrix![K::ZERO; m, cols];
+        let mut e = vector![K::ZERO; m];
+
+        for j in 0..cols {
+            e[j] = K::ONE;
+            let col = self.apply_q(&e);
+            for i in 0..m {
+                q[(i, j)] = col[i];
+            }
+            e[j] = K::ZERO;
+        }
+
+        q
+    }
+
+    // Full factorization: Q is m x m, R is m x n
+    pub fn q(&self) -> Matrix<K> {
+        self.q_cols(self.n_rows())
+    }
+
+    pub fn r(&self) -> Matrix<K> {
+        self.r.clone()
+    }
+
+    // Economy factorization: Q is m x k, R is k x n with k = min(m, n)
+    pub fn thin_q(&self) -> Matrix<K> {
+        self.q_cols(self.n_rows().min(self.n_cols()))
+    }
+
+    pub fn thin_r(&self) -> Matrix<K> {
+        let k = self.n_rows().min(self.n_cols());
+        let mut r = matrix![K::ZERO; k, self.n_cols()];
+
+        for i in 0..k {
+            for j in i..self.n_cols() {
+                r[(i, j)] = self.r[(i, j)];
+            }
+        }
+
+        r
+    }
+
+    pub fn is_rank_deficient(&self) -> bool {
+        let k = self.n_rows().min(self.n_cols());
+        let mut max = K::ZERO;
+        for i in 0..k {
+            if self.r[(i, i)].abs() > max {
+                max = self.r[(i, i)].abs();
+            }
+        }
+
+        let tol = K::EPSILON * max * K::from_f64(self.n_rows().max(self.n_cols()) as f64);
+        k < self.n_cols() || (0..k).any(|i| self.r[(i, i)].abs() <= tol)
+    }
+
+    // Minimizes ||Ax - b||, returns x together with the residual norm
+    pub fn least_squares(&self, b: &Vector<K>) -> Result<(Vector<K>, K), MatrixError> {
+        let (m, n) = (self.n_rows(), self.n_cols());
+
+        if b.len() != m {
+            return Err(MatrixError::size("least_squares", m, b.len()));
+        }
+
+        if self.is_rank_deficient() {
+            return Err(MatrixError::Singular);
+        }
+
+        let y = self.apply_qt(b);
+        let mut x = vector![K::ZERO; n];
+
+        for i in (0..n).rev() {
+            let mut acc = y[i];
+            for j in (i + 1)..n {
+                acc -= &(self.r[(i, j)] * x[j]);
+            }
+            x[i] = acc / self.r[(i, i)];
+        }
+
+        let residual = if m > n {
+            Vector::from(y.slice(n..m)).norm()
+        } else {
+            K::ZERO
+        };
+
+        Ok((x, residual))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::approx::assert_matrix_approx_eq;
+
+    #[test]
+    fn qr_test() {
+        let a = matrix![[12., -51., 4.], [6., 167., -68.], [-4., 24., -41.]];
+        let qr = a.qr();
+        let (q, r) = (qr.q(), qr.r());
+
+        assert_matrix_approx_eq!(q.mul_mat(&r), a, 1e-12);
+        assert_matrix_approx_eq!(q.transpose().mul_mat(&q), a.identity(), 1e-12);
+        assert_matrix_approx_eq!(
+            r,
+            matrix![[-14., -21., 14.], [0., -175., 70.], [0., 0., -35.]],
+            1e-12
+        );
+    }
+
+    #[test]
+    fn qr_rectangular_test() {
+        let a = matrix![[1., 2.], [3., 4.], [5., 6.], [7., 8.]];
+        let qr = a.qr();
+
+        assert_eq!(qr.q().shape(), matrix![0.; 4, 4].shape());
+        assert_eq!(qr.r().shape(), matrix![0.; 4, 2].shape());
+        assert_matrix_approx_eq!(qr.q().mul_mat(&qr.r()), a, 1e-12);
+
+        let (q, r) = (qr.thin_q(), qr.thin_r());
+
+        assert_eq!(q.shape(), matrix![0.; 4, 2].shape());
+        assert_eq!(r.shape(), matrix![0.; 2, 2].shape());
+        assert_matrix_approx_eq!(q.mul_mat(&r), a, 1e-12);
+        assert_matrix_approx_eq!(
+            q.transpose().mul_mat(&q),
+            matrix![[1., 0.], [0., 1.]],
+            1e-12
+        );
+        assert_eq!(r[(1, 0)], 0.);
+    }
+
+    #[test]
+    fn least_squares_test() {
+        // Fit y = c0 + c1 t through (0, 1), (1, 3), (2, 4), (3, 4)
+        let a: Matrix<f64> = matrix![[1., 0.], [1., 1.], [1., 2.], [1., 3.]];
+        let (x, residual) = a.least_squares(&vector![1., 3., 4., 4.]).unwrap();
+
+        assert!((x[0] - 1.5).abs() < 1e-12);
+        assert!((x[1] - 1.).abs() < 1e-12);
+
+        let mut r = a.mul_vec(&x);
+        r.sub(&vector![1., 3., 4., 4.]);
+        assert!((residual - r.norm()).abs() < 1e-12);
+
+        let (x, residual) = a.least_squares(&vector![1., 2., 3., 4.]).unwrap();
+
+        assert!((x[0] - 1.).abs() < 1e-12 && (x[1] - 1.).abs() < 1e-12);
+        assert!(residual < 1e-12);
+    }
+
+    #[test]
+    fn least_squares_error_test() {
+        let a = matrix![[1., 2.], [2., 4.], [3., 6.]];
+
+        assert!(matches!(
+            a.least_squares(&vector![1., 2., 3.]),
+            Err(MatrixError::Singular)
+        ));
+        assert!(matches!(
+            a.least_squares(&vector![1., 2.]),
+            Err(MatrixError::Incompatible { .. })
+        ));
+    }
+}
@@ -0,0 +1,379 @@
+use super::FieldBound;
+
+use num_integer::Integer;
+use num_traits::{CheckedAdd, CheckedMul, CheckedNeg, CheckedSub};
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops;
+
+// Primitive signed integers a Rational is built on
+pub trait IntegerBound:
+    FieldBound + Integer + Copy + CheckedAdd + CheckedSub + CheckedMul + CheckedNeg
+{
+}
+
+impl<T> IntegerBound for T where
+    T: FieldBound + Integer + Copy + CheckedAdd + CheckedSub + CheckedMul + CheckedNeg
+{
+}
+
+fn overflow<T>(v: Option<T>) -> T {
+    match v {
+        Some(v) => v,
+        None => panic!("rational arithmetic overflowed"),
+    }
+}
+
+// Exact fraction num/den over one of the integer FieldBound types.
+// Always kept normalized: gcd(num, den) = 1 and den > 0. Arithmetic is
+// checked and panics on overflow instead of wrapping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rational<T: IntegerBound> {
+    num: T,
+    den: T,
+}
+
+impl<T: IntegerBound> Rational<T> {
+    pub fn new(num: T, den: T) -> Self {
+        if den == T::ZERO {
+            panic!("rational with a zero denominator");
+        }
+
+        let g = Integer::gcd(&num, &den);
+        let (mut num, mut den) = (num / g, den / g);
+        if den < T::ZERO {
+            num = overflow(num.checked_neg());
+            den = overflow(den.checked_neg());
+        }
+
+        Rational { num, den }
+    }
+
+    pub fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        let g = Integer::gcd(&self.den, &rhs.den);
+        let num = self
+            .num
+            .checked_mul(&(rhs.den / g))?
+            .checked_add(&rhs.num.checked_mul(&(self.den / g))?)?;
+        let den = self.den.checked_mul(&(rhs.den / g))?;
+        Some(Rational::new(num, den))
+    }
+
+    pub fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        self.checked_add(&Rational {
+            num: rhs.num.checked_neg()?,
+            den: rhs.den,
+        })
+    }
+
+    pub fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+        // Cross-cancel first to keep the products small
+        let g1 = Integer::gcd(&self.num, &rhs.den);
+        let g2 = Integer::gcd(&rhs.num, &self.den);
+        if g1 == T::ZERO || g2 == T::ZERO {
+            return Some(Self::ZERO);
+        }
+        Some(Rational::new(
+            (self.num / g1).checked_mul(&(rhs.num / g2))?,
+            (self.den / g2).checked_mul(&(rhs.den / g1))?,
+        ))
+    }
+
+    // None on overflow, panics on division by zero like `/`
+    pub fn checked_div(&self, rhs: &Self) -> Option<Self> {
+        if rhs.num == T::ZERO {
+            panic!("attempt to divide a rational by zero");
+        }
+        self.checked_mul(&rhs.recip())
+    }
+
+    pub fn numer(&self) -> T {
+        self.num
+    }
+
+    pub fn denom(&self) -> T {
+        self.den
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.den == T::ONE
+    }
+
+    pub fn recip(&self) -> Self {
+        Rational::new(self.den, self.num)
+    }
+}
+
+impl<T: IntegerBound> From<T> for Rational<T> {
+    fn from(num: T) -> Self {
+        Rational { num, den: T::ONE }
+    }
+}
+
+impl<T: IntegerBound> fmt::Display for Rational<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+impl<T: IntegerBound> PartialOrd for Rational<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if let (Some(a), Some(b)) = (
+            self.num.checked_mul(&other.den),
+            other.num.checked_mul(&self.den),
+        ) {
+            return a.partial_cmp(&b);
+        }
+
+        // Cross products overflow: compare the integer parts, then the
+        // reciprocals of the fractional parts in reverse
+        let (qa, ra) = self.num.div_mod_floor(&self.den);
+        let (qb, rb) = other.num.div_mod_floor(&other.den);
+        if qa != qb {
+            return qa.partial_cmp(&qb);
+        }
+        match (ra == T::ZERO, rb == T::ZERO) {
+            (true, true) => Some(Ordering::Equal),
+            (true, false) => Some(Ordering::Less),
+            (false, true) => Some(Ordering::Greater),
+            (false, false) => {
+                Rational::new(other.den, rb).partial_cmp(&Rational::new(self.den, ra))
+            }
+        }
+    }
+}
+
+impl<T: IntegerBound> ops::Neg for Rational<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Rational {
+            num: overflow(self.num.checked_neg()),
+            den: self.den,
+        }
+    }
+}
+
+impl<T: IntegerBound> ops::Add for Rational<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        overflow(self.checked_add(&rhs))
+    }
+}
+
+impl<T: IntegerBound> ops::Sub for Rational<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        overflow(self.checked_sub(&rhs))
+    }
+}
+
+impl<T: IntegerBound> ops::Mul for Rational<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        overflow(self.checked_mul(&rhs))
+    }
+}
+
+impl<T: IntegerBound> ops::Div for Rational<T> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        overflow(self.checked_div(&rhs))
+    }
+}
+
+macro_rules! impl_ops_assign {
+    ($trait:ty, $fun:ident, $op:tt) => {
+        impl<T: IntegerBound> $trait for Rational<T> {
+            fn $fun(&mut self, rhs: &Self) {
+                *self = *self $op *rhs;
+            }
+        }
+    };
+}
+
+impl_ops_assign!(ops::AddAssign<&Self>, add_assign, +);
+impl_ops_assign!(ops::SubAssign<&Self>, sub_assign, -);
+impl_ops_assign!(ops::MulAssign<&Self>, mul_assign, *);
+impl_ops_assign!(ops::DivAssign<&Self>, div_assign, /);
+
+impl<T: IntegerBound> FieldBound for Rational<T> {
+    const ZERO: Self = Rational {
+        num: T::ZERO,
+        den: T::ONE,
+    };
+    const ONE: Self = Rational {
+        num: T::ONE,
+        den: T::ONE,
+    };
+
+    fn abs(&self) -> Self {
+        if self.num < T::ZERO {
+            -*self
+        } else {
+            *self
+        }
+    }
+
+    // Only defined when numerator and denominator are perfect squares, since
+    // any other root is irrational
+    fn sqrt(&self) -> Self {
+        if self.num < T::ZERO {
+            panic!("square root of a negative rational {}", self);
+        }
+
+        let (n, d) = (FieldBound::sqrt(&self.num), FieldBound::sqrt(&self.den));
+        if n.checked_mul(&n) != Some(self.num) || d.checked_mul(&d) != Some(self.den) {
+            panic!("square root of {} is not rational", self);
+        }
+        Rational { num: n, den: d }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.num == T::ZERO
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::matrix::matrix;
+    use crate::matrix::Matrix;
+    use crate::vector::vector;
+
+    fn r(num: i64, den: i64) -> Rational<i64> {
+        Rational::new(num, den)
+    }
+
+    #[test]
+    fn normalize_test() {
+        assert_eq!(r(6, -14), r(-3, 7));
+        assert_eq!(r(-6, -14).numer(), 3);
+        assert_eq!(r(-6, -14).denom(), 7);
+        assert_eq!(r(0, -5), Rational::ZERO);
+        assert!(r(8, 4).is_integer());
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_denominator_test() {
+        r(1, 0);
+    }
+
+    #[test]
+    fn arithmetic_test() {
+        assert_eq!(r(1, 2) + r(1, 3), r(5, 6));
+        assert_eq!(r(1, 2) - r(1, 3), r(1, 6));
+        assert_eq!(r(2, 3) * r(9, 4), r(3, 2));
+        assert_eq!(r(2, 3) / r(4, 9), r(3, 2));
+        assert_eq!(-r(2, 3), r(-2, 3));
+        assert_eq!(r(0, 1) * r(5, 7), Rational::ZERO);
+        assert!(r(1, 3) < r(1, 2));
+        assert!(r(-1, 2) < r(-1, 3));
+
+        let mut a = r(1, 4);
+        a += &r(1, 4);
+        a /= &r(3, 1);
+        assert_eq!(a, r(1, 6));
+    }
+
+    #[test]
+    fn field_bound_test() {
+        assert_eq!(r(-9, 4).abs(), r(9, 4));
+        assert_eq!(r(9, 4).sqrt(), r(3, 2));
+        assert!(r(0, 3).is_zero());
+        assert_eq!(Rational::from(5i64), r(5, 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "not rational")]
+    fn irrational_sqrt_test() {
+        r(2, 1).sqrt();
+    }
+
+    #[test]
+    fn overflow_test() {
+        let big = r(i64::MAX, 1);
+
+        assert_eq!(big.checked_add(&r(1, 1)), None);
+        assert_eq!(big.checked_mul(&r(2, 1)), None);
+        assert_eq!(r(i64::MIN + 1, 1).checked_sub(&r(2, 1)), None);
+        assert_eq!(big.checked_div(&r(1, 2)), None);
+        assert_eq!(big.checked_mul(&r(1, 3)), Some(r(i64::MAX, 3)));
+        // Ordering stays exact when the cross products overflow
+        assert!(r(i64::MAX - 1, i64::MAX) > r(i64::MAX - 2, i64::MAX - 1));
+        assert!(r(i64::MAX, 3) > r(i64::MAX - 1, 3));
+    }
+
+    #[test]
+    #[should_panic(expected = "overflowed")]
+    fn overflow_panic_test() {
+        let _ = r(i64::MAX, 1) + r(1, 1);
+    }
+
+    #[test]
+    fn display_test() {
+        assert_eq!(r(3, 7).to_string(), "3/7");
+        assert_eq!(r(-6, 3).to_string(), "-2");
+        assert_eq!(format!("{:.2}", r(1, 2)), "1/2");
+    }
+
+    #[test]
+    fn row_echelon_test() {
+        let m = matrix![
+            [r(2, 1), r(1, 1), r(-1, 1), r(8, 1)],
+            [r(-3, 1), r(-1, 1), r(2, 1), r(-11, 1)],
+            [r(-2, 1), r(1, 1), r(2, 1), r(-3, 1)]
+        ];
+
+        assert_eq!(
+            m.row_echelon(),
+            matrix![
+                [r(1, 1), r(0, 1), r(0, 1), r(2, 1)],
+                [r(0, 1), r(1, 1), r(0, 1), r(3, 1)],
+                [r(0, 1), r(0, 1), r(1, 1), r(-1, 1)]
+            ]
+        );
+
+        let m = matrix![[r(3, 1), r(1, 1), r(1, 1)], [r(1, 1), r(3, 1), r(1, 1)]];
+
+        assert_eq!(
+            m.row_echelon(),
+            matrix![[r(1, 1), r(0, 1), r(1, 4)], [r(0, 1), r(1, 1), r(1, 4)]]
+        );
+    }
+
+    #[test]
+    fn matrix_test() {
+        let m: Matrix<Rational<i64>> = matrix![
+            [r(2, 1), r(1, 1), r(1, 1)],
+            [r(1, 1), r(3, 1), r(2, 1)],
+            [r(1, 1), r(0, 1), r(0, 1)]
+        ];
+
+        assert_eq!(m.determinant(), r(-1, 1));
+        assert_eq!(
+            m.inverse().unwrap(),
+            matrix![
+                [r(0, 1), r(0, 1), r(1, 1)],
+                [r(-2, 1), r(1, 1), r(3, 1)],
+                [r(3, 1), r(-1, 1), r(-5, 1)]
+            ]
+        );
+        assert_eq!(m.mul_mat(&m.inverse().unwrap()), m.identity());
+        assert_eq!(
+            m.solve(&vector![r(1, 2), r(1, 3), r(1, 4)]).unwrap(),
+            vector![r(1, 4), r(1, 12), r(-1, 12)]
+        );
+
+        let singular = matrix![[r(1, 2), r(1, 3)], [r(3, 2), r(1, 1)]];
+
+        assert_eq!(singular.rank(), 1);
+        assert!(singular.inverse().is_err());
+    }
+}
@@ -0,0 +1,854 @@
+use super::Dimension;
+use super::FieldBound;
+use super::MatrixError;
+use super::VectorSpace;
+use super::D2;
+
+use super::matrix::Matrix;
+use super::vector::Vector;
+
+// Triplet builder. Entries may be pushed in any order, duplicates are summed
+// when converting to a compressed format.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CooMatrix<K: FieldBound> {
+    rows: usize,
+    cols: usize,
+    entries: Vec<(usize, usize, K)>,
+}
+
+// Shared storage of CSR and CSC: `outer` is the compressed axis (rows for CSR,
+// columns for CSC). Within each outer slot the inner indices are strictly
+// increasing and no explicit zero is stored, so equal matrices compare equal.
+#[derive(Debug, Clone, PartialEq)]
+struct Compressed<K: FieldBound> {
+    outer: usize,
+    inner: usize,
+    indptr: Vec<usize>,
+    indices: Vec<usize>,
+    values: Vec<K>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsrMatrix<K: FieldBound> {
+    storage: Compressed<K>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CscMatrix<K: FieldBound> {
+    storage: Compressed<K>,
+}
+
+fn check_bounds(i: usize, j: usize, rows: usize, cols: usize) {
+    if i >= rows || j >= cols {
+        panic!(
+            "index ({}, {}) out of bounds for a {}x{} matrix",
+            i, j, rows, cols
+        );
+    }
+}
+
+fn shape_of(rows: usize, cols: usize) -> Dimension {
+    Dimension::D2(D2 { rows, cols })
+}
+
+// Compressed
+
+impl<K: FieldBound> Compressed<K> {
+    fn empty(outer: usize, inner: usize) -> Self {
+        Compressed {
+            outer,
+            inner,
+            indptr: vec![0; outer + 1],
+            indices: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+
+    fn from_triplets(outer: usize, inner: usize, mut triplets: Vec<(usize, usize, K)>) -> Self {
+        triplets.sort_by_key(|&(o, i, _)| (o, i));
+
+        let mut c = Compressed::empty(outer, inner);
+        let mut last = None;
+        for (o, i, k) in triplets {
+            if last == Some((o, i)) {
+                *c.values.last_mut().unwrap() += &k;
+            } else {
+                c.indices.push(i);
+                c.values.push(k);
+                c.indptr[o + 1] += 1;
+                last = Some((o, i));
+            }
+        }
+        for o in 0..outer {
+            c.indptr[o + 1] += c.indptr[o];
+        }
+
+        c.prune();
+        c
+    }
+
+    // Drops entries that became zero (cancelled duplicates, add, scl by zero)
+    fn prune(&mut self) {
+        let mut kept = 0;
+        let mut start = 0;
+        for o in 0..self.outer {
+            let end = self.indptr[o + 1];
+            for p in start..end {
+                if !self.values[p].is_zero() {
+                    self.indices.swap(kept, p);
+                    self.values.swap(kept, p);
+                    kept += 1;
+                }
+            }
+            start = end;
+            self.indptr[o + 1] = kept;
+        }
+        self.indices.truncate(kept);
+        self.values.truncate(kept);
+    }
+
+    fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    fn slot(&self, o: usize) -> (&[usize], &[K]) {
+        let (start, end) = (self.indptr[o], self.indptr[o + 1]);
+        (&self.indices[start..end], &self.values[start..end])
+    }
+
+    fn get(&self, o: usize, i: usize) -> Option<&K> {
+        let (indices, values) = self.slot(o);
+        indices.binary_search(&i).ok().map(|p| &values[p])
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (usize, usize, &K)> + '_ {
+        (0..self.outer).flat_map(move |o| {
+            let (indices, values) = self.slot(o);
+            indices
+                .iter()
+                .zip(values.iter())
+                .map(move |(&i, k)| (o, i, k))
+        })
+    }
+
+    // Same entries compressed along the other axis, by counting sort
+    fn recompress(&self) -> Self {
+        let mut indptr = vec![0; self.inner + 1];
+        for &i in &self.indices {
+            indptr[i + 1] += 1;
+        }
+        for i in 0..self.inner {
+            indptr[i + 1] += indptr[i];
+        }
+
+        let mut next = indptr.clone();
+        let mut indices = vec![0; self.nnz()];
+        let mut values = vec![K::ZERO; self.nnz()];
+        for (o, i, k) in self.iter() {
+            indices[next[i]] = o;
+            values[next[i]] = k.clone();
+            next[i] += 1;
+        }
+
+        Compressed {
+            outer: self.inner,
+            inner: self.outer,
+            indptr,
+            indices,
+            values,
+        }
+    }
+
+    // Entrywise self + sign * other, merging the sorted slots
+    fn combine(&self, other: &Self, subtract: bool) -> Self {
+        let mut c = Compressed::empty(self.outer, self.inner);
+        let rhs = |k: &K| if subtract { -k.clone() } else { k.clone() };
+
+        for o in 0..self.outer {
+            let (ai, av) = self.slot(o);
+            let (bi, bv) = other.slot(o);
+            let (mut p, mut q) = (0, 0);
+
+            while p < ai.len() || q < bi.len() {
+                let (i, k) = if q == bi.len() || (p < ai.len() && ai[p] < bi[q]) {
+                    p += 1;
+                    (ai[p - 1], av[p - 1].clone())
+                } else if p == ai.len() || bi[q] < ai[p] {
+                    q += 1;
+                    (bi[q - 1], rhs(&bv[q - 1]))
+                } else {
+                    let mut k = av[p].clone();
+                    k += &rhs(&bv[q]);
+                    p += 1;
+                    q += 1;
+                    (ai[p - 1], k)
+                };
+
+                if !k.is_zero() {
+                    c.indices.push(i);
+                    c.values.push(k);
+                }
+            }
+            c.indptr[o + 1] = c.indices.len();
+        }
+
+        c
+    }
+
+    fn scl(&mut self, a: &K) {
+        for k in &mut self.values {
+            *k *= a;
+        }
+        self.prune();
+    }
+
+    // Gustavson's row-by-row product: self is outer x k, other is k x inner
+    fn product(&self, other: &Self) -> Self {
+        let mut c = Compressed::empty(self.outer, other.inner);
+        let mut acc = vec![K::ZERO; other.inner];
+        let mut touched = vec![false; other.inner];
+        let mut pattern = Vec::new();
+
+        for o in 0..self.outer {
+            let (ai, av) = self.slot(o);
+            for (&k, a_ok) in ai.iter().zip(av) {
+                let (bi, bv) = other.slot(k);
+                for (&j, b_kj) in bi.iter().zip(bv) {
+                    if !touched[j] {
+                        touched[j] = true;
+                        pattern.push(j);
+                    }
+                    acc[j] += &(a_ok.clone() * b_kj.clone());
+                }
+            }
+
+            pattern.sort_unstable();
+            for &j in &pattern {
+                let k = std::mem::replace(&mut acc[j], K::ZERO);
+                touched[j] = false;
+                if !k.is_zero() {
+                    c.indices.push(j);
+                    c.values.push(k);
+                }
+            }
+            pattern.clear();
+            c.indptr[o + 1] = c.indices.len();
+        }
+
+        c
+    }
+
+    fn sqsum(&self) -> K {
+        let mut sqsum = K::ZERO;
+        for x in &self.values {
+            let mut sq = x.clone();
+            sq *= &x.conjugate();
+            sqsum += &sq;
+        }
+        sqsum
+    }
+
+    fn sum(&self) -> K {
+        let mut sum = K::ZERO;
+        for x in &self.values {
+            sum += x;
+        }
+        sum
+    }
+
+    fn norm_1(&self) -> K {
+        let mut sum = K::ZERO;
+        for x in &self.values {
+            sum += &x.abs();
+        }
+        sum
+    }
+
+    fn norm_inf(&self) -> K {
+        let mut max = K::ZERO;
+        for x in &self.values {
+            if x.abs() > max {
+                max = x.abs();
+            }
+        }
+        max
+    }
+}
+
+// COO
+
+impl<K: FieldBound> CooMatrix<K> {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        CooMatrix {
+            rows,
+            cols,
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn n_rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn n_cols(&self) -> usize {
+        self.cols
+    }
+
+    // Number of pushed triplets, duplicates included
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn push(&mut self, i: usize, j: usize, k: K) {
+        check_bounds(i, j, self.rows, self.cols);
+        self.entries.push((i, j, k));
+    }
+
+    pub fn to_csr(&self) -> CsrMatrix<K> {
+        CsrMatrix {
+            storage: Compressed::from_triplets(self.rows, self.cols, self.entries.clone()),
+        }
+    }
+
+    pub fn to_csc(&self) -> CscMatrix<K> {
+        let transposed = self.entries.iter().map(|(i, j, k)| (*j, *i, k.clone()));
+        CscMatrix {
+            storage: Compressed::from_triplets(self.cols, self.rows, transposed.collect()),
+        }
+    }
+}
+
+// CSR
+
+impl<K: FieldBound> CsrMatrix<K> {
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        CsrMatrix {
+            storage: Compressed::empty(rows, cols),
+        }
+    }
+
+    pub fn n_rows(&self) -> usize {
+        self.storage.outer
+    }
+
+    pub fn n_cols(&self) -> usize {
+        self.storage.inner
+    }
+
+    // Number of stored (non-zero) entries
+    pub fn nnz(&self) -> usize {
+        self.storage.nnz()
+    }
+
+    pub fn indptr(&self) -> &[usize] {
+        &self.storage.indptr
+    }
+
+    pub fn indices(&self) -> &[usize] {
+        &self.storage.indices
+    }
+
+    pub fn values(&self) -> &[K] {
+        &self.storage.values
+    }
+
+    // Rows already sorted and free of duplicates and zeros
+    pub(crate) fn from_raw_parts(
+        rows: usize,
+        cols: usize,
+        indptr: Vec<usize>,
+        indices: Vec<usize>,
+        values: Vec<K>,
+    ) -> Self {
+        debug_assert_eq!(indptr.len(), rows + 1);
+        CsrMatrix {
+            storage: Compressed {
+                outer: rows,
+                inner: cols,
+                indptr,
+                indices,
+                values,
+            },
+        }
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> K {
+        check_bounds(i, j, self.n_rows(), self.n_cols());
+        self.storage.get(i, j).cloned().unwrap_or(K::ZERO)
+    }
+
+    // Column indices and values of row i
+    pub fn row(&self, i: usize) -> (&[usize], &[K]) {
+        self.storage.slot(i)
+    }
+
+    // Stored entries as (row, col, value), row by row
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, &K)> + '_ {
+        self.storage.iter()
+    }
+
+    pub fn transpose(&self) -> CsrMatrix<K> {
+        CsrMatrix {
+            storage: self.storage.recompress(),
+        }
+    }
+
+    pub fn to_csc(&self) -> CscMatrix<K> {
+        CscMatrix {
+            storage: self.storage.recompress(),
+        }
+    }
+
+    pub fn to_dense(&self) -> Matrix<K> {
+        let mut data = vec![K::ZERO; self.n_rows() * self.n_cols()];
+        for (i, j, k) in self.iter() {
+            data[j * self.n_rows() + i] = k.clone();
+        }
+        Matrix::from_col_major(self.n_rows(), self.n_cols(), data)
+    }
+
+    pub fn mul_vec(&self, v: &Vector<K>) -> Vector<K> {
+        match self.try_mul_vec(v) {
+            Ok(u) => u,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    pub fn try_mul_vec(&self, v: &Vector<K>) -> Result<Vector<K>, MatrixError> {
+        if v.len() != self.n_cols() {
+            return Err(MatrixError::size("mul_vec", self.n_cols(), v.len()));
+        }
+
+        let mut y = Vec::with_capacity(self.n_rows());
+        for i in 0..self.n_rows() {
+            let (indices, values) = self.row(i);
+            let mut y_i = K::ZERO;
+            for (&j, a_ij) in indices.iter().zip(values) {
+                y_i += &(a_ij.clone() * v[j].clone());
+            }
+            y.push(y_i);
+        }
+
+        Ok(Vector::from(y))
+    }
+
+    pub fn mul_mat(&self, m: &CsrMatrix<K>) -> CsrMatrix<K> {
+        match self.try_mul_mat(m) {
+            Ok(p) => p,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    pub fn try_mul_mat(&self, m: &CsrMatrix<K>) -> Result<CsrMatrix<K>, MatrixError> {
+        if self.n_cols() != m.n_rows() {
+            return Err(MatrixError::incompatible(
+                "mul_mat",
+                shape_of(self.n_cols(), m.n_cols()),
+                m.shape(),
+            ));
+        }
+
+        Ok(CsrMatrix {
+            storage: self.storage.product(&m.storage),
+        })
+    }
+}
+
+impl<K: FieldBound> From<&Matrix<K>> for CsrMatrix<K> {
+    fn from(m: &Matrix<K>) -> Self {
+        CscMatrix::from(m).to_csr()
+    }
+}
+
+impl<K: FieldBound> From<&CooMatrix<K>> for CsrMatrix<K> {
+    fn from(m: &CooMatrix<K>) -> Self {
+        m.to_csr()
+    }
+}
+
+impl<K: FieldBound> From<&CsrMatrix<K>> for Matrix<K> {
+    fn from(m: &CsrMatrix<K>) -> Self {
+        m.to_dense()
+    }
+}
+
+impl<K: FieldBound> VectorSpace for CsrMatrix<K> {
+    type Field = K;
+
+    fn shape(&self) -> Dimension {
+        shape_of(self.n_rows(), self.n_cols())
+    }
+
+    fn size(&self) -> usize {
+        self.n_rows() * self.n_cols()
+    }
+
+    fn add(&mut self, v: &Self) {
+        self.force_eq_shape(v);
+        self.storage = self.storage.combine(&v.storage, false);
+    }
+
+    fn sub(&mut self, v: &Self) {
+        self.force_eq_shape(v);
+        self.storage = self.storage.combine(&v.storage, true);
+    }
+
+    fn scl(&mut self, a: K) {
+        self.storage.scl(&a);
+    }
+
+    fn sum(&self) -> K {
+        self.storage.sum()
+    }
+
+    fn sqsum(&self) -> K {
+        self.storage.sqsum()
+    }
+
+    fn norm_inf(&self) -> K {
+        self.storage.norm_inf()
+    }
+
+    fn norm_1(&self) -> K {
+        self.storage.norm_1()
+    }
+
+    fn norm(&self) -> K {
+        self.sqsum().sqrt()
+    }
+}
+
+// CSC
+
+impl<K: FieldBound> CscMatrix<K> {
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        CscMatrix {
+            storage: Compressed::empty(cols, rows),
+        }
+    }
+
+    pub fn n_rows(&self) -> usize {
+        self.storage.inner
+    }
+
+    pub fn n_cols(&self) -> usize {
+        self.storage.outer
+    }
+
+    pub fn nnz(&self) -> usize {
+        self.storage.nnz()
+    }
+
+    pub fn indptr(&self) -> &[usize] {
+        &self.storage.indptr
+    }
+
+    pub fn indices(&self) -> &[usize] {
+        &self.storage.indices
+    }
+
+    pub fn values(&self) -> &[K] {
+        &self.storage.values
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> K {
+        check_bounds(i, j, self.n_rows(), self.n_cols());
+        self.storage.get(j, i).cloned().unwrap_or(K::ZERO)
+    }
+
+    // Row indices and values of column j
+    pub fn col(&self, j: usize) -> (&[usize], &[K]) {
+        self.storage.slot(j)
+    }
+
+    // Stored entries as (row, col, value), column by column
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, &K)> + '_ {
+        self.storage.iter().map(|(j, i, k)| (i, j, k))
+    }
+
+    pub fn transpose(&self) -> CscMatrix<K> {
+        CscMatrix {
+            storage: self.storage.recompress(),
+        }
+    }
+
+    pub fn to_csr(&self) -> CsrMatrix<K> {
+        CsrMatrix {
+            storage: self.storage.recompress(),
+        }
+    }
+
+    pub fn to_dense(&self) -> Matrix<K> {
+        let mut data = vec![K::ZERO; self.n_rows() * self.n_cols()];
+        for (i, j, k) in self.iter() {
+            data[j * self.n_rows() + i] = k.clone();
+        }
+        Matrix::from_col_major(self.n_rows(), self.n_cols(), data)
+    }
+
+    pub fn mul_vec(&self, v: &Vector<K>) -> Vector<K> {
+        match self.try_mul_vec(v) {
+            Ok(u) => u,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    pub fn try_mul_vec(&self, v: &Vector<K>) -> Result<Vector<K>, MatrixError> {
+        if v.len() != self.n_cols() {
+            return Err(MatrixError::size("mul_vec", self.n_cols(), v.len()));
+        }
+
+        // Sum of the columns scaled by the entries of v
+        let mut y = vec![K::ZERO; self.n_rows()];
+        for j in 0..self.n_cols() {
+            let (indices, values) = self.col(j);
+            for (&i, a_ij) in indices.iter().zip(values) {
+                y[i] += &(a_ij.clone() * v[j].clone());
+            }
+        }
+
+        Ok(Vector::from(y))
+    }
+
+    pub fn mul_mat(&self, m: &CscMatrix<K>) -> CscMatrix<K> {
+        match self.try_mul_mat(m) {
+            Ok(p) => p,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    pub fn try_mul_mat(&self, m: &CscMatrix<K>) -> Result<CscMatrix<K>, MatrixError> {
+        if self.n_cols() != m.n_rows() {
+            return Err(MatrixError::incompatible(
+                "mul_mat",
+                shape_of(self.n_cols(), m.n_cols()),
+                m.shape(),
+            ));
+        }
+
+        // The CSC storage of A is the CSR storage of A^T, and (AB)^T = B^T A^T
+        Ok(CscMatrix {
+            storage: m.storage.product(&self.storage),
+        })
+    }
+}
+
+impl<K: FieldBound> From<&Matrix<K>> for CscMatrix<K> {
+    fn from(m: &Matrix<K>) -> Self {
+        let mut c = Compressed::empty(m.n_cols(), m.n_rows());
+        for j in 0..m.n_cols() {
+            for (i, k) in m.col(j).iter().enumerate() {
+                if !k.is_zero() {
+                    c.indices.push(i);
+                    c.values.push(k.clone());
+                }
+            }
+            c.indptr[j + 1] = c.indices.len();
+        }
+        CscMatrix { storage: c }
+    }
+}
+
+impl<K: FieldBound> From<&CooMatrix<K>> for CscMatrix<K> {
+    fn from(m: &CooMatrix<K>) -> Self {
+        m.to_csc()
+    }
+}
+
+impl<K: FieldBound> From<&CscMatrix<K>> for Matrix<K> {
+    fn from(m: &CscMatrix<K>) -> Self {
+        m.to_dense()
+    }
+}
+
+impl<K: FieldBound> VectorSpace for CscMatrix<K> {
+    type Field = K;
+
+    fn shape(&self) -> Dimension {
+        shape_of(self.n_rows(), self.n_cols())
+    }
+
+    fn size(&self) -> usize {
+        self.n_rows() * self.n_cols()
+    }
+
+    fn add(&mut self, v: &Self) {
+        self.force_eq_shape(v);
+        self.storage = self.storage.combine(&v.storage, false);
+    }
+
+    fn sub(&mut self, v: &Self) {
+        self.force_eq_shape(v);
+        self.storage = self.storage.combine(&v.storage, true);
+    }
+
+    fn scl(&mut self, a: K) {
+        self.storage.scl(&a);
+    }
+
+    fn sum(&self) -> K {
+        self.storage.sum()
+    }
+
+    fn sqsum(&self) -> K {
+        self.storage.sqsum()
+    }
+
+    fn norm_inf(&self) -> K {
+        self.storage.norm_inf()
+    }
+
+    fn norm_1(&self) -> K {
+        self.storage.norm_1()
+    }
+
+    fn norm(&self) -> K {
+        self.sqsum().sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::fixtures::{poisson, sparse_sample as sample};
+    use crate::matrix::matrix;
+    use crate::vector::vector;
+
+    #[test]
+    fn coo_test() {
+        let mut coo = CooMatrix::new(3, 4);
+        coo.push(2, 1, 3);
+        coo.push(0, 3, -1);
+        coo.push(0, 0, 4);
+        coo.push(1, 2, 2);
+        coo.push(2, 0, 1);
+        // Duplicates are summed, cancelled entries are not stored
+        coo.push(1, 1, 5);
+        coo.push(1, 1, -5);
+
+        let csr = coo.to_csr();
+        assert_eq!(coo.len(), 7);
+        assert_eq!(csr.nnz(), 5);
+        assert_eq!(csr.indptr(), &[0, 2, 3, 5]);
+        assert_eq!(csr.indices(), &[0, 3, 2, 0, 1]);
+        assert_eq!(csr.values(), &[4, -1, 2, 1, 3]);
+        assert_eq!(csr, CsrMatrix::from(&sample()));
+
+        let csc = coo.to_csc();
+        assert_eq!(csc.indptr(), &[0, 2, 3, 4, 5]);
+        assert_eq!(csc.indices(), &[0, 2, 2, 1, 0]);
+        assert_eq!(csc, CscMatrix::from(&sample()));
+        assert_eq!(csc.get(2, 1), 3);
+        assert_eq!(csc.get(1, 1), 0);
+    }
+
+    #[test]
+    fn conversion_test() {
+        let m = sample();
+        let csr = CsrMatrix::from(&m);
+        let csc = CscMatrix::from(&m);
+
+        assert_eq!(Matrix::from(&csr), m);
+        assert_eq!(Matrix::from(&csc), m);
+        assert_eq!(csr.to_csc(), csc);
+        assert_eq!(csc.to_csr(), csr);
+        assert_eq!(csr.transpose().to_dense(), m.transpose());
+        assert_eq!(csc.transpose().to_dense(), m.transpose());
+        assert_eq!(
+            csr.iter().map(|(i, j, k)| (i, j, *k)).collect::<Vec<_>>(),
+            vec![(0, 0, 4), (0, 3, -1), (1, 2, 2), (2, 0, 1), (2, 1, 3)]
+        );
+    }
+
+    #[test]
+    fn mul_vec_test() {
+        let m = sample();
+        let v = vector![1, -1, 2, 3];
+
+        assert_eq!(CsrMatrix::from(&m).mul_vec(&v), m.mul_vec(&v));
+        assert_eq!(CscMatrix::from(&m).mul_vec(&v), m.mul_vec(&v));
+        assert!(CsrMatrix::from(&m).try_mul_vec(&vector![1, 2]).is_err());
+        assert!(CscMatrix::from(&m).try_mul_vec(&vector![1, 2]).is_err());
+
+        // 0 * inf is NaN and must reach the product
+        let a = CscMatrix::from(&matrix![[f64::INFINITY, 1.]]);
+        assert!(a.mul_vec(&vector![0., 2.])[0].is_nan());
+        assert!(
+            CsrMatrix::from(&matrix![[f64::INFINITY, 1.]]).mul_vec(&vector![0., 2.])[0].is_nan()
+        );
+    }
+
+    #[test]
+    fn mul_mat_test() {
+        let a = sample();
+        let b = matrix![[1, 0], [0, 2], [-1, 0], [4, 1]];
+        let expected = a.mul_mat(&b);
+
+        let csr = CsrMatrix::from(&a).mul_mat(&CsrMatrix::from(&b));
+        let csc = CscMatrix::from(&a).mul_mat(&CscMatrix::from(&b));
+        assert_eq!(csr.to_dense(), expected);
+        assert_eq!(csc.to_dense(), expected);
+
+        // Cancellation leaves no explicit zero behind
+        let p = CsrMatrix::from(&matrix![[1, 1]]).mul_mat(&CsrMatrix::from(&matrix![[1], [-1]]));
+        assert_eq!(p.nnz(), 0);
+
+        assert!(CsrMatrix::from(&a)
+            .try_mul_mat(&CsrMatrix::from(&a))
+            .is_err());
+    }
+
+    #[test]
+    fn vector_space_test() {
+        let a = sample();
+        let b = matrix![[-4, 1, 0, 0], [0, 0, 0, 0], [1, 0, 0, 2]];
+
+        let mut csr = CsrMatrix::from(&a);
+        csr.add(&CsrMatrix::from(&b));
+        let mut dense = a.clone();
+        dense.add(&b);
+        assert_eq!(csr.to_dense(), dense);
+        assert_eq!(csr.nnz(), 6);
+
+        let mut csc = CscMatrix::from(&a);
+        csc.sub(&CscMatrix::from(&b));
+        let mut dense = a.clone();
+        dense.sub(&b);
+        assert_eq!(csc.to_dense(), dense);
+
+        csc.scl(0);
+        assert_eq!(csc.nnz(), 0);
+        assert!(csr.try_add(&CsrMatrix::zeros(4, 3)).is_err());
+
+        let s = CsrMatrix::from(&a);
+        assert_eq!(s.sum(), a.sum());
+        assert_eq!(s.norm_1(), a.norm_1());
+        assert_eq!(s.norm_inf(), a.norm_inf());
+        assert_eq!(s.sqsum(), a.sqsum());
+    }
+
+    #[test]
+    fn large_test() {
+        // 100k x 100k tridiagonal [-1, 2, -1], far too large for a dense Matrix
+        let n = 100_000;
+        let csr = poisson(n);
+        assert_eq!(csr.nnz(), 3 * n - 2);
+
+        // Constant vectors are only changed at the two boundary rows
+        let y = csr.mul_vec(&vector![1.; n]);
+        assert_eq!(y[0], 1.);
+        assert_eq!(y[n / 2], 0.);
+        assert_eq!(y[n - 1], 1.);
+        assert_eq!(csr.to_csc().mul_vec(&vector![1.; n]), y);
+
+        let sq = csr.mul_mat(&csr);
+        assert_eq!(sq.nnz(), 5 * n - 6);
+        assert_eq!(sq.get(n / 2, n / 2), 6.);
+        assert_eq!(csr.transpose(), csr);
+    }
+}
@@ -0,0 +1,308 @@
+use super::FloatBound;
+use super::VectorSpace;
+
+use super::iterative::{
+    axpy, check_system, default_max_iterations, default_tolerance, finish, ConvergenceReport,
+    IterativeError, Solution,
+};
+use super::matrix::Matrix;
+use super::preconditioner::{IdentityPreconditioner, Preconditioner};
+use super::vector::vector;
+use super::vector::Vector;
+
+use std::cmp::Ordering;
+
+// A sweep is reported as diverging once its residual exceeds the smallest one
+// seen so far by this factor, or stops being a number
+const DIVERGENCE_GROWTH: f64 = 1e4;
+
+impl<K: FloatBound> Matrix<K> {
+    pub fn jacobi(&self, b: &Vector<K>) -> Solution<K> {
+        self.jacobi_with(
+            b,
+            K::ONE,
+            default_tolerance(),
+            default_max_iterations(b.len()),
+        )
+    }
+
+    // Weighted Jacobi, omega = 1 is the classical method
+    pub fn jacobi_with(&self, b: &Vector<K>, omega: K, tol: K, max_sweeps: usize) -> Solution<K> {
+        self.stationary("jacobi", b, omega, false, tol, max_sweeps)
+    }
+
+    pub fn gauss_seidel(&self, b: &Vector<K>) -> Solution<K> {
+        self.sor_with(
+            b,
+            K::ONE,
+            default_tolerance(),
+            default_max_iterations(b.len()),
+        )
+    }
+
+    pub fn gauss_seidel_with(&self, b: &Vector<K>, tol: K, max_sweeps: usize) -> Solution<K> {
+        self.sor_with(b, K::ONE, tol, max_sweeps)
+    }
+
+    // Successive over-relaxation, converges for SPD matrices when 0 < omega < 2
+    pub fn sor(&self, b: &Vector<K>, omega: K) -> Solution<K> {
+        self.sor_with(
+            b,
+            omega,
+            default_tolerance(),
+            default_max_iterations(b.len()),
+        )
+    }
+
+    pub fn sor_with(&self, b: &Vector<K>, omega: K, tol: K, max_sweeps: usize) -> Solution<K> {
+        self.stationary("sor", b, omega, true, tol, max_sweeps)
+    }
+
+    // Preconditioned Richardson iteration x <- x + omega M^{-1} (b - A x).
+    // Converges when the spectral radius of I - omega M^{-1} A is below one,
+    // e.g. with the Jacobi preconditioner and omega = 1 it is Jacobi's method.
+    pub fn richardson(&self, b: &Vector<K>, omega: K) -> Solution<K> {
+        self.richardson_with(
+            b,
+            omega,
+            default_tolerance(),
+            default_max_iterations(b.len()),
+        )
+    }
+
+    pub fn richardson_with(
+        &self,
+        b: &Vector<K>,
+        omega: K,
+        tol: K,
+        max_sweeps: usize,
+    ) -> Solution<K> {
+        self.richardson_preconditioned(b, &IdentityPreconditioner, omega, tol, max_sweeps)
+    }
+
+    pub fn richardson_preconditioned<M>(
+        &self,
+        b: &Vector<K>,
+        m: &M,
+        omega: K,
+        tol: K,
+        max_sweeps: usize,
+    ) -> Solution<K>
+    where
+        M: Preconditioner<K> + ?Sized,
+    {
+        check_system("richardson", self, b)?;
+
+        self.sweeps(b, tol, max_sweeps, |x, _| {
+            let mut r = b.clone();
+            r.sub(&self.mul_vec(x));
+            axpy(x, omega, &m.apply(&r));
+        })
+    }
+
+    // x_i <- (1 - omega) x_i + omega (b_i - sum_{j != i} a_ij x_j) / a_ii, with
+    // the sum over the previous sweep (Jacobi) or the latest values (SOR)
+    fn stationary(
+        &self,
+        operation: &'static str,
+        b: &Vector<K>,
+        omega: K,
+        in_place: bool,
+        tol: K,
+        max_sweeps: usize,
+    ) -> Solution<K> {
+        check_system(operation, self, b)?;
+
+        let n = b.len();
+        if (0..n).any(|i| self[(i, i)].is_zero()) {
+            return Err(IterativeError::Breakdown { iterations: 0 });
+        }
+
+        self.sweeps(b, tol, max_sweeps, |x, previous| {
+            for i in 0..n {
+                let source = if in_place { &*x } else { previous };
+                let mut sigma = b[i];
+                for j in 0..n {
+                    if j != i {
+                        sigma -= &(self[(i, j)] * source[j]);
+                    }
+                }
+                x[i] = (K::ONE - omega) * previous[i] + omega * sigma / self[(i, i)];
+            }
+        })
+    }
+
+    // Runs sweep(x, previous) from x = 0 until convergence, recording the
+    // residual and spectral radius estimate of every sweep
+    fn sweeps<F>(&self, b: &Vector<K>, tol: K, max_sweeps: usize, mut sweep: F) -> Solution<K>
+    where
+        F: FnMut(&mut Vector<K>, &Vector<K>),
+    {
+        let mut x = vector![K::ZERO; b.len()];
+        let b_norm = b.norm();
+        if b_norm.is_zero() {
+            return Ok((x, ConvergenceReport::new(K::ZERO)));
+        }
+
+        let mut report = ConvergenceReport::new(K::ONE);
+        let mut best = K::ONE;
+        let mut last_step: Option<K> = None;
+
+        while report.iterations() < max_sweeps && report.residual() > tol {
+            let previous = x.clone();
+            sweep(&mut x, &previous);
+
+            // ||x_{k+1} - x_k|| / ||x_k - x_{k-1}|| tends to the spectral
+            // radius of the iteration matrix
+            let mut step = x.clone();
+            step.sub(&previous);
+            let step = step.norm();
+            let rho = match last_step {
+                Some(last) if !last.is_zero() => Some(step / last),
+                _ => None,
+            };
+            last_step = Some(step);
+
+            let mut r = b.clone();
+            r.sub(&self.mul_vec(&x));
+            let residual = r.norm() / b_norm;
+            report.push_sweep(residual, rho);
+
+            // NaN residuals are unordered
+            match residual.partial_cmp(&(best * K::from_f64(DIVERGENCE_GROWTH))) {
+                Some(Ordering::Greater) | None => return Err(IterativeError::Diverged(report)),
+                _ => {}
+            }
+            if residual < best {
+                best = residual;
+            }
+        }
+
+        finish(x, report, tol)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::approx::assert_vector_approx_eq;
+    use crate::fixtures;
+    use crate::matrix::matrix;
+    use crate::preconditioner::JacobiPreconditioner;
+    use crate::MatrixError;
+
+    fn poisson(n: usize) -> Matrix<f64> {
+        fixtures::poisson(n).to_dense()
+    }
+
+    #[test]
+    fn jacobi_test() {
+        let a = matrix![
+            [10., -1., 2., 0.],
+            [-1., 11., -1., 3.],
+            [2., -1., 10., -1.],
+            [0., 3., -1., 8.]
+        ];
+        let b = vector![6., 25., -11., 15.];
+
+        let (x, report) = a.jacobi(&b).unwrap();
+        assert_vector_approx_eq!(x, vector![1., 2., -1., 1.], 1e-6);
+        assert!(report.spectral_radius().unwrap() < 1.);
+        assert_eq!(report.residuals().len(), report.iterations() + 1);
+        assert_eq!(report.spectral_radii().len(), report.iterations());
+        assert_eq!(report.spectral_radii()[0], None);
+    }
+
+    #[test]
+    fn spectral_radius_test() {
+        // Jacobi iteration matrix of tridiag(-1, 2, -1) has rho = cos(pi / (n + 1))
+        let n = 10;
+        let a = poisson(n);
+        let b = vector![1.; n];
+        let rho = (std::f64::consts::PI / (n + 1) as f64).cos();
+
+        let (_, jacobi) = a.jacobi_with(&b, 1., 1e-12, 2000).unwrap();
+        assert!((jacobi.spectral_radius().unwrap() - rho).abs() < 1e-3);
+
+        // The estimates settle on rho as the sweeps go on
+        let radii = jacobi.spectral_radii();
+        let error = |k: usize| (radii[k].unwrap() - rho).abs();
+        assert!(error(radii.len() - 1) < error(2));
+
+        // Gauss-Seidel squares it
+        let (x, gs) = a.gauss_seidel_with(&b, 1e-12, 2000).unwrap();
+        assert!((gs.spectral_radius().unwrap() - rho * rho).abs() < 1e-3);
+        assert!(gs.iterations() < jacobi.iterations());
+        assert_vector_approx_eq!(x, a.solve(&b).unwrap(), 1e-9);
+
+        // Optimal relaxation 2 / (1 + sin(pi / (n + 1)))
+        let omega = 2. / (1. + (std::f64::consts::PI / (n + 1) as f64).sin());
+        let (x, sor) = a.sor_with(&b, omega, 1e-12, 2000).unwrap();
+        assert!(sor.iterations() < gs.iterations());
+        assert_vector_approx_eq!(x, a.solve(&b).unwrap(), 1e-9);
+    }
+
+    #[test]
+    fn richardson_test() {
+        // Eigenvalues of tridiag(-1, 2, -1) lie in (0, 4), so omega < 1/2 converges
+        let a = poisson(10);
+        let b = vector![1.; 10];
+
+        let (x, report) = a.richardson_with(&b, 0.45, 1e-10, 5000).unwrap();
+        assert_vector_approx_eq!(x, a.solve(&b).unwrap(), 1e-8);
+        assert_eq!(report.spectral_radii().len(), report.iterations());
+        assert!(matches!(
+            a.richardson(&b, 1.),
+            Err(IterativeError::Diverged(_))
+        ));
+
+        // M = diag(A) reproduces Jacobi sweep for sweep
+        let d = JacobiPreconditioner::new(&a).unwrap();
+        let (x, report) = a
+            .richardson_preconditioned(&b, &d, 1., 1e-10, 2000)
+            .unwrap();
+        let (y, jacobi) = a.jacobi_with(&b, 1., 1e-10, 2000).unwrap();
+        assert_vector_approx_eq!(x, y, 1e-12);
+        assert_eq!(report.iterations(), jacobi.iterations());
+    }
+
+    #[test]
+    fn divergence_test() {
+        // Not diagonally dominant, the Jacobi iteration matrix has rho = 2
+        let a = matrix![[1., 2.], [2., 1.]];
+        match a.jacobi(&vector![1., 1.]) {
+            Err(IterativeError::Diverged(report)) => {
+                assert!(report.iterations() < 20);
+                assert!((report.spectral_radius().unwrap() - 2f64).abs() < 1e-9);
+            }
+            other => panic!("expected Diverged, got {:?}", other),
+        }
+
+        // Over-relaxation beyond 2 diverges even for SPD matrices
+        assert!(matches!(
+            poisson(5).sor(&vector![1.; 5], 2.5),
+            Err(IterativeError::Diverged(_))
+        ));
+    }
+
+    #[test]
+    fn stationary_errors_test() {
+        assert_eq!(
+            matrix![[0., 1.], [1., 0.]].gauss_seidel(&vector![1., 1.]),
+            Err(IterativeError::Breakdown { iterations: 0 })
+        );
+        assert!(matches!(
+            poisson(3).jacobi(&vector![1., 1.]),
+            Err(IterativeError::Matrix(MatrixError::Incompatible { .. }))
+        ));
+        assert!(matches!(
+            poisson(20).gauss_seidel_with(&vector![1.; 20], 1e-12, 5),
+            Err(IterativeError::NoConvergence(_))
+        ));
+
+        let (x, report) = poisson(3).sor(&vector![0.; 3], 1.5).unwrap();
+        assert_eq!(x, vector![0.; 3]);
+        assert_eq!(report.iterations(), 0);
+    }
+}
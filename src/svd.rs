@@ -0,0 +1,436 @@
+use super::FloatBound;
+use super::MatrixError;
+
+use super::matrix::matrix;
+use super::matrix::Matrix;
+use super::vector::vector;
+use super::vector::Vector;
+
+use std::cmp::Ordering;
+
+const DEFAULT_MAX_SWEEPS: usize = 100;
+
+// A = U diag(s) V^T with singular values in descending order.
+// U (m x m) and V (n x n) are stored complete, the thin accessors
+// keep their first min(m, n) columns.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SVD<K: FloatBound> {
+    u: Matrix<K>,
+    s: Vector<K>,
+    v: Matrix<K>,
+}
+
+fn column_dot<K: FloatBound>(a: &Matrix<K>, p: usize, b: &Matrix<K>, q: usize) -> K {
+    let mut d = K::ZERO;
+    for i in 0..a.n_rows() {
+        d += &(a[(i, p)] * b[(i, q)]);
+    }
+    d
+}
+
+fn rotate_columns<K: FloatBound>(a: &mut Matrix<K>, p: usize, q: usize, c: K, s: K) {
+    for i in 0..a.n_rows() {
+        let (ap, aq) = (a[(i, p)], a[(i, q)]);
+        a[(i, p)] = c * ap - s * aq;
+        a[(i, q)] = s * ap + c * aq;
+    }
+}
+
+// Fills the columns of u not flagged in `valid` with an orthonormal
+// complement of the flagged ones (Gram-Schmidt over the unit vectors)
+fn complete_basis<K: FloatBound>(u: &mut Matrix<K>, valid: &mut [bool]) {
+    let m = u.n_rows();
+    let half = K::from_f64(0.5);
+
+    for j in 0..valid.len() {
+        if valid[j] {
+            continue;
+        }
+
+        for e in 0..m {
+            let mut w = vector![K::ZERO; m];
+            w[e] = K::ONE;
+
+            // Orthogonalize twice for stability
+            for _ in 0..2 {
+                for k in (0..valid.len()).filter(|&k| valid[k]) {
+                    let mut d = K::ZERO;
+                    for i in 0..m {
+                        d += &(u[(i, k)] * w[i]);
+                    }
+                    for i in 0..m {
+                        w[i] -= &(d * u[(i, k)]);
+                    }
+                }
+            }
+
+            let mut norm = K::ZERO;
+            for i in 0..m {
+                norm += &(w[i] * w[i]);
+            }
+            let norm = norm.sqrt();
+
+            if norm > half {
+                for i in 0..m {
+                    u[(i, j)] = w[i] / norm;
+                }
+                valid[j] = true;
+                break;
+            }
+        }
+    }
+}
+
+impl<K: FloatBound> Matrix<K> {
+    pub fn svd(&self) -> Result<SVD<K>, MatrixError> {
+        self.svd_with(K::EPSILON, DEFAULT_MAX_SWEEPS)
+    }
+
+    // One-sided (Hestenes) Jacobi: columns are rotated until every pair is
+    // orthogonal up to tol, or fails after max_sweeps sweeps
+    pub fn svd_with(&self, tol: K, max_sweeps: usize) -> Result<SVD<K>, MatrixError> {
+        if self.n_rows() < self.n_cols() {
+            let t = self.transpose().svd_with(tol, max_sweeps)?;
+            return Ok(SVD {
+                u: t.v,
+                s: t.s,
+                v: t.u,
+            });
+        }
+
+        let (m, n) = (self.n_rows(), self.n_cols());
+        let mut w = self.clone();
+        let mut v = matrix![K::ZERO; n];
+        for i in 0..n {
+            v[(i, i)] = K::ONE;
+        }
+
+        let mut sweeps = 0;
+        loop {
+            let mut rotated = false;
+
+            for p in 0..n {
+                for q in (p + 1)..n {
+                    let alpha = column_dot(&w, p, &w, p);
+                    let beta = column_dot(&w, q, &w, q);
+                    let gamma = column_dot(&w, p, &w, q);
+
+                    if gamma.abs() <= tol * (alpha * beta).sqrt() || gamma.is_zero() {
+                        continue;
+                    }
+                    rotated = true;
+
+                    let zeta = (beta - alpha) / (K::from_f64(2.) * gamma);
+                    let mut t = K::ONE / (zeta.abs() + (K::ONE + zeta * zeta).sqrt());
+                    if zeta < K::ZERO {
+                        t = -t;
+                    }
+                    let c = K::ONE / (K::ONE + t * t).sqrt();
+                    let s = c * t;
+
+                    rotate_columns(&mut w, p, q, c, s);
+                    rotate_columns(&mut v, p, q, c, s);
+                }
+            }
+
+            if !rotated {
+                break;
+            }
+            // A cap of zero only accepts input that is already orthogonal
+            sweeps += 1;
+            if sweeps >= max_sweeps {
+                return Err(MatrixError::NoConvergence {
+                    iterations: max_sweeps,
+                });
+            }
+        }
+
+        let sigma: Vec<K> = (0..n).map(|j| column_dot(&w, j, &w, j).sqrt()).collect();
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&i, &j| sigma[j].partial_cmp(&sigma[i]).unwrap_or(Ordering::Equal));
+
+        let cutoff = if n > 0 {
+            sigma[order[0]] * K::EPSILON * K::from_f64(m as f64)
+        } else {
+            K::ZERO
+        };
+
+        let mut u = matrix![K::ZERO; m];
+        let mut sorted_v = matrix![K::ZERO; n];
+        let mut s = vector![K::ZERO; n];
+        let mut valid = vec![false; m];
+
+        for (k, &j) in order.iter().enumerate() {
+            s[k] = sigma[j];
+            for i in 0..n {
+                sorted_v[(i, k)] = v[(i, j)];
+            }
+            if sigma[j] > cutoff {
+                for i in 0..m {
+                    u[(i, k)] = w[(i, j)] / sigma[j];
+                }
+                valid[k] = true;
+            }
+        }
+        complete_basis(&mut u, &mut valid);
+
+        Ok(SVD { u, s, v: sorted_v })
+    }
+}
+
+impl<K: FloatBound> SVD<K> {
+    fn k(&self) -> usize {
+        self.s.len()
+    }
+
+    fn leading_columns(m: &Matrix<K>, cols: usize) -> Matrix<K> {
+        let mut r = matrix![K::ZERO; m.n_rows(), cols];
+        for j in 0..cols {
+            for i in 0..m.n_rows() {
+                r[(i, j)] = m[(i, j)];
+            }
+        }
+        r
+    }
+
+    pub fn u(&self) -> Matrix<K> {
+        self.u.clone()
+    }
+
+    pub fn singular_values(&self) -> Vector<K> {
+        self.s.clone()
+    }
+
+    pub fn vt(&self) -> Matrix<K> {
+        self.v.transpose()
+    }
+
+    pub fn thin_u(&self) -> Matrix<K> {
+        Self::leading_columns(&self.u, self.k())
+    }
+
+    pub fn thin_vt(&self) -> Matrix<K> {
+        Self::leading_columns(&self.v, self.k()).transpose()
+    }
+
+    // Singular values at or below max(m, n) * s_max * EPSILON count as zero
+    pub fn default_tolerance(&self) -> K {
+        let dim = self.u.n_rows().max(self.v.n_rows());
+        if self.k() == 0 {
+            return K::ZERO;
+        }
+        self.s[0] * K::EPSILON * K::from_f64(dim as f64)
+    }
+
+    pub fn rank(&self) -> usize {
+        self.rank_with_tol(self.default_tolerance())
+    }
+
+    pub fn rank_with_tol(&self, tol: K) -> usize {
+        self.s.iter().filter(|&&s| s > tol).count()
+    }
+
+    // Zero for an empty matrix
+    pub fn norm_2(&self) -> K {
+        if self.k() == 0 {
+            return K::ZERO;
+        }
+        self.s[0]
+    }
+
+    // s_max / s_min, infinite when s_min is within default_tolerance() of
+    // zero, so exactly when rank() < min(m, n). Zero for an empty matrix.
+    pub fn condition_number(&self) -> K {
+        let k = self.k();
+        if k == 0 {
+            return K::ZERO;
+        }
+        if self.s[k - 1] <= self.default_tolerance() {
+            return K::from_f64(f64::INFINITY);
+        }
+        self.s[0] / self.s[k - 1]
+    }
+
+    // V diag(1 / s) U^T, where singular values at or below tol are treated
+    // as exact zeros and left out. For rank-deficient input this gives the
+    // minimum-norm least-squares solution operator; a tol under the noise
+    // level lets the reciprocals of tiny singular values dominate.
+    pub fn pseudo_inverse(&self, tol: K) -> Matrix<K> {
+        let (m, n) = (self.u.n_rows(), self.v.n_rows());
+        let mut pinv = matrix![K::ZERO; n, m];
+
+        for k in 0..self.k() {
+            if self.s[k] <= tol {
+                break;
+            }
+            for i in 0..n {
+                let f = self.v[(i, k)] / self.s[k];
+                for j in 0..m {
+                    pinv[(i, j)] += &(f * self.u[(j, k)]);
+                }
+            }
+        }
+
+        pinv
+    }
+}
+
+impl<K: FloatBound> Matrix<K> {
+    // Moore-Penrose pseudo-inverse of any shape, see SVD::pseudo_inverse
+    pub fn pseudo_inverse(&self, tol: K) -> Result<Matrix<K>, MatrixError> {
+        Ok(self.svd()?.pseudo_inverse(tol))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::approx::assert_matrix_approx_eq;
+    use crate::VectorSpace;
+
+    fn reconstruct(u: &Matrix<f64>, s: &Vector<f64>, vt: &Matrix<f64>) -> Matrix<f64> {
+        let mut sigma = matrix![0.; u.n_cols(), vt.n_rows()];
+        for k in 0..s.len() {
+            sigma[(k, k)] = s[k];
+        }
+        u.mul_mat(&sigma).mul_mat(vt)
+    }
+
+    #[test]
+    fn svd_test() {
+        let a: Matrix<f64> = matrix![[3., 2., 2.], [2., 3., -2.]];
+        let svd = a.svd().unwrap();
+        let s = svd.singular_values();
+
+        assert!((s[0] - 5.).abs() < 1e-12 && (s[1] - 3.).abs() < 1e-12);
+        assert_eq!(svd.u().shape(), matrix![0.; 2, 2].shape());
+        assert_eq!(svd.vt().shape(), matrix![0.; 3, 3].shape());
+        assert_matrix_approx_eq!(reconstruct(&svd.u(), &s, &svd.vt()), a, 1e-10);
+        assert_matrix_approx_eq!(
+            svd.vt().mul_mat(&svd.vt().transpose()),
+            matrix![0.; 3].identity(),
+            1e-10
+        );
+    }
+
+    #[test]
+    fn thin_svd_test() {
+        let a = matrix![[1., 2.], [3., 4.], [5., 6.], [7., 8.]];
+        let svd = a.svd().unwrap();
+        let (u, vt) = (svd.thin_u(), svd.thin_vt());
+
+        assert_eq!(u.shape(), matrix![0.; 4, 2].shape());
+        assert_eq!(vt.shape(), matrix![0.; 2, 2].shape());
+        assert_matrix_approx_eq!(reconstruct(&u, &svd.singular_values(), &vt), a, 1e-10);
+        assert_matrix_approx_eq!(
+            u.transpose().mul_mat(&u),
+            matrix![[1., 0.], [0., 1.]],
+            1e-10
+        );
+
+        let full = svd.u();
+        assert_matrix_approx_eq!(
+            full.transpose().mul_mat(&full),
+            matrix![0.; 4].identity(),
+            1e-10
+        );
+    }
+
+    #[test]
+    fn svd_rank_test() {
+        let a = matrix![[1., 2., 3.], [2., 4., 6.], [1., 0., 1.], [0., 1., 1.]];
+        let svd = a.svd().unwrap();
+
+        assert_eq!(svd.rank(), 2);
+        assert!(svd.singular_values()[2] < 1e-12);
+        assert_matrix_approx_eq!(
+            reconstruct(&svd.u(), &svd.singular_values(), &svd.vt()),
+            a,
+            1e-10
+        );
+    }
+
+    #[test]
+    fn norm_2_condition_number_test() {
+        let a = matrix![[3., 0.], [4., 5.]];
+        let svd = a.svd().unwrap();
+
+        // Singular values are 3 sqrt(5) and sqrt(5)
+        assert!((svd.norm_2() - 45f64.sqrt()).abs() < 1e-12);
+        assert!((svd.condition_number() - 3.).abs() < 1e-12);
+        let singular: Matrix<f64> = matrix![[1., 1.], [1., 1.]];
+
+        assert!(singular.svd().unwrap().condition_number().is_infinite());
+
+        // Rounding leaves s_min near 1e-16 rather than exactly zero
+        let singular: Matrix<f64> = matrix![[1., 2., 3.], [4., 5., 6.], [7., 8., 9.]];
+        let svd = singular.svd().unwrap();
+        assert_eq!(svd.rank(), 2);
+        assert!(svd.condition_number().is_infinite());
+
+        let empty = Matrix::from(Vector::from(Vec::<Vector<f64>>::new()));
+        let svd = empty.svd().unwrap();
+        assert_eq!(svd.norm_2(), 0.);
+        assert_eq!(svd.condition_number(), 0.);
+    }
+
+    #[test]
+    fn pseudo_inverse_test() {
+        let a = matrix![[4., 7.], [2., 6.]];
+        let b = matrix![[1., 0.], [0., 1.], [0., 0.]];
+
+        assert_matrix_approx_eq!(
+            a.pseudo_inverse(1e-12).unwrap(),
+            a.inverse().unwrap(),
+            1e-10
+        );
+        assert_matrix_approx_eq!(
+            b.pseudo_inverse(1e-12).unwrap(),
+            matrix![[1., 0., 0.], [0., 1., 0.]],
+            1e-10
+        );
+    }
+
+    #[test]
+    fn pseudo_inverse_rank_deficient_test() {
+        let a = matrix![[1., 2.], [2., 4.]];
+        let c = matrix![[1., 2., 3.], [2., 4., 6.], [1., 0., 1.], [0., 1., 1.]];
+        let pinv = a.pseudo_inverse(1e-12).unwrap();
+
+        // Rank one: A+ = A^T / ||A||^2
+        assert_matrix_approx_eq!(pinv, matrix![[0.04, 0.08], [0.08, 0.16]], 1e-10);
+
+        // Moore-Penrose conditions
+        let svd = c.svd().unwrap();
+        let pinv = svd.pseudo_inverse(svd.default_tolerance());
+
+        assert_matrix_approx_eq!(c.mul_mat(&pinv).mul_mat(&c), c, 1e-10);
+        assert_matrix_approx_eq!(pinv.mul_mat(&c).mul_mat(&pinv), pinv, 1e-10);
+        let cp = c.mul_mat(&pinv);
+        assert_matrix_approx_eq!(cp, cp.transpose(), 1e-10);
+        let pc = pinv.mul_mat(&c);
+        assert_matrix_approx_eq!(pc, pc.transpose(), 1e-10);
+    }
+
+    #[test]
+    fn svd_error_test() {
+        let a = matrix![[1., 2.], [3., 4.]];
+
+        assert_eq!(
+            a.svd_with(1e-15, 1).unwrap_err(),
+            MatrixError::NoConvergence { iterations: 1 }
+        );
+        assert_eq!(
+            a.svd_with(1e-300, 0).unwrap_err(),
+            MatrixError::NoConvergence { iterations: 0 }
+        );
+
+        // Orthogonal columns need no rotation at all
+        let d = matrix![[3., 0.], [0., 2.]];
+        assert_eq!(
+            d.svd_with(1e-15, 0).unwrap().singular_values(),
+            vector![3., 2.]
+        );
+    }
+}
@@ -1,5 +1,7 @@
+use super::matrix::Matrix;
 use super::Dimension;
 use super::FieldBound;
+use super::MatrixError;
 use super::VectorSpace;
 use super::D1;
 use std::fmt;
@@ -77,11 +79,10 @@ impl<K: fmt::Display + FieldBound> fmt::Display for Vector<K> {
             content.push_str(&num.to_string());
             content.push_str(", ");
         }
-        match content.rfind(", ") {
-            Some(i) => content.replace_range(i..content.len(), ""),
-            None => {}
+        if let Some(i) = content.rfind(", ") {
+            content.replace_range(i..content.len(), "")
         }
-        content.push_str("]");
+        content.push(']');
         write!(f, "{}", content)
     }
 }
@@ -142,7 +143,9 @@ macro_rules! impl_ops_assign {
             K: FieldBound,
         {
             fn $fun(&mut self, rhs: &Self) {
-                self.force_eq_size(&rhs);
+                if self.size() != rhs.size() {
+                    panic!("{}", MatrixError::size(stringify!($fun), self.size(), rhs.size()));
+                }
 
                 let mut v_iter = rhs.into_iter();
                 for i in &mut self.fields {
@@ -190,7 +193,11 @@ where
     }
 
     fn is_zero(&self) -> bool {
-        self.len() == 0
+        self.is_empty()
+    }
+
+    fn conjugate(&self) -> Self {
+        Vector::conjugate(self)
     }
 }
 
@@ -277,11 +284,11 @@ impl<K: FieldBound> VectorSpace for Vector<K> {
         }
 
         let mut sqsum: K = self.fields[0].clone();
-        sqsum *= &sqsum.clone();
+        sqsum *= &sqsum.conjugate();
 
         for i in 1..self.len() {
             let mut field = self.fields[i].clone();
-            field *= &field.clone();
+            field *= &field.conjugate();
             sqsum += &field;
         }
 
@@ -331,6 +338,10 @@ where
         self.size()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.size() == 0
+    }
+
     pub fn iter(&self) -> slice::Iter<'_, K> {
         self.fields.iter()
     }
@@ -339,10 +350,42 @@ where
         self.fields.push(k);
     }
 
+    // Conjugate-linear in self: sum of conj(self_i) * v_i
     pub fn dot(&self, v: &Vector<K>) -> K {
-        let mut c = self.clone();
+        match self.try_dot(v) {
+            Ok(d) => d,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    pub fn try_dot(&self, v: &Vector<K>) -> Result<K, MatrixError> {
+        if self.is_empty() {
+            return Err(MatrixError::Empty { operation: "dot" });
+        }
+        if self.len() != v.len() {
+            return Err(MatrixError::size("dot", self.len(), v.len()));
+        }
+
+        let mut c = self.conjugate();
         c *= v;
-        c.sum()
+        Ok(c.sum())
+    }
+
+    pub fn conjugate(&self) -> Vector<K> {
+        let mut v = self.clone();
+
+        for k in &mut v {
+            *k = k.conjugate();
+        }
+
+        v
+    }
+
+    // Row (1 x n) matrix holding the conjugated entries
+    pub fn conjugate_transpose(&self) -> Matrix<K> {
+        let mut m = Matrix::from(vector![self.conjugate()]);
+        m.transpose_mut();
+        m
     }
 
     pub fn slice(&self, range: ops::Range<usize>) -> &[K] {
@@ -377,7 +420,7 @@ where
     K: FieldBound,
 {
     let mut slide = v.clone();
-    slide.sub(&u);
+    slide.sub(u);
     slide.scl(t);
 
     let mut interp = u.clone();
@@ -403,23 +446,38 @@ pub fn cross_product<K>(u: &Vector<K>, v: &Vector<K>) -> Vector<K>
 where
     K: FieldBound,
 {
-    if u.size() != 3 || v.size() != 3 {
-        panic!("vectors are not 3 dimensional");
+    match try_cross_product(u, v) {
+        Ok(w) => w,
+        Err(e) => panic!("{}", e),
+    }
+}
+
+pub fn try_cross_product<K>(u: &Vector<K>, v: &Vector<K>) -> Result<Vector<K>, MatrixError>
+where
+    K: FieldBound,
+{
+    for w in [u, v] {
+        if w.size() != 3 {
+            return Err(MatrixError::size("cross_product", 3, w.size()));
+        }
     }
 
-    vector![
+    Ok(vector![
         (u[1].clone() * v[2].clone() - u[2].clone() * v[1].clone()), // + (a2 b3 - a3 b2)
         (u[2].clone() * v[0].clone() - u[0].clone() * v[2].clone()), // - (a3 b1 - a1 b3)
         (u[0].clone() * v[1].clone() - u[1].clone() * v[0].clone())  // + (a1 b2 - a2 b1)
-    ]
+    ])
 }
 
 // Tests
 
+// The tests spell out identities and patterns on purpose
 #[cfg(test)]
+#[allow(clippy::redundant_pattern_matching)]
 mod tests {
 
     use super::*;
+    use crate::complex::Complex;
 
     #[test]
     fn vector_macro_test() {
@@ -574,6 +632,35 @@ mod tests {
         assert_eq!(cross_product(&v3, &v4), vector![17, -58, -16]);
     }
 
+    #[test]
+    fn try_ops_test() {
+        let u = vector![1, 2, 3];
+        let v = vector![0, 1];
+
+        assert_eq!(u.try_dot(&u), Ok(14));
+        assert_eq!(
+            u.try_dot(&v),
+            Err(MatrixError::Incompatible {
+                operation: "dot",
+                expected: Dimension::D1(D1 { length: 3 }),
+                actual: Dimension::D1(D1 { length: 2 }),
+            })
+        );
+        assert_eq!(try_cross_product(&u, &u), Ok(vector![0, 0, 0]));
+        assert!(try_cross_product(&u, &v).is_err());
+
+        let mut w = u.clone();
+        assert!(w.try_add(&v).is_err());
+        assert!(w.try_add(&u).is_ok());
+        assert_eq!(w, vector![2, 4, 6]);
+
+        let e = u.eq_size_compatible(&v).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            "size check: received incompatible object of shape 2, expected 3"
+        );
+    }
+
     #[test]
     #[should_panic]
     fn force_compatibility_test() {
@@ -590,6 +677,27 @@ mod tests {
         assert_eq!(-v1, vector![-1, -2, -3]);
     }
 
+    #[test]
+    fn conjugate_test() {
+        let v = vector![Complex::new(1., 2.), Complex::new(-3., 0.)];
+
+        assert_eq!(
+            v.conjugate(),
+            vector![Complex::new(1., -2.), Complex::new(-3., 0.)]
+        );
+        assert_eq!(vector![1, 2].conjugate(), vector![1, 2]);
+    }
+
+    #[test]
+    fn conjugate_transpose_test() {
+        let v = vector![Complex::new(1., 2.), Complex::new(0., -1.)];
+        let vh = v.conjugate_transpose();
+
+        assert_eq!(vh[(0, 0)], Complex::new(1., -2.));
+        assert_eq!(vh[(0, 1)], Complex::new(0., 1.));
+        assert_eq!(vh.mul_vec(&v), vector![v.dot(&v)]);
+    }
+
     #[test]
     fn slice_test() {
         let v = vector![10, 11, 12, 13, 14];
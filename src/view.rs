@@ -0,0 +1,612 @@
+use super::Dimension;
+use super::FieldBound;
+use super::MatrixError;
+use super::D2;
+
+use super::matrix::Matrix;
+use super::vector::Vector;
+
+use std::ops;
+
+// Borrowed block of a matrix. Entry (i, j) lives at i * row_step + j * col_step
+// of the borrowed buffer, so sub-blocks, single rows and transposes are all
+// expressed by the two steps without copying.
+#[derive(Debug, Clone, Copy)]
+pub struct MatrixView<'a, K: FieldBound> {
+    data: &'a [K],
+    rows: usize,
+    cols: usize,
+    row_step: usize,
+    col_step: usize,
+}
+
+#[derive(Debug)]
+pub struct MatrixViewMut<'a, K: FieldBound> {
+    data: &'a mut [K],
+    rows: usize,
+    cols: usize,
+    row_step: usize,
+    col_step: usize,
+}
+
+// Start offset and length of the buffer covered by a block. An empty block
+// covers nothing, and its start may lie past the end of the buffer.
+fn block_span(
+    (r0, c0): (usize, usize),
+    (rows, cols): (usize, usize),
+    (row_step, col_step): (usize, usize),
+) -> (usize, usize) {
+    if rows == 0 || cols == 0 {
+        return (0, 0);
+    }
+    (
+        r0 * row_step + c0 * col_step,
+        (rows - 1) * row_step + (cols - 1) * col_step + 1,
+    )
+}
+
+fn check_ranges(
+    rows: &ops::Range<usize>,
+    cols: &ops::Range<usize>,
+    (n_rows, n_cols): (usize, usize),
+) {
+    if rows.start > rows.end || rows.end > n_rows || cols.start > cols.end || cols.end > n_cols {
+        panic!(
+            "view [{:?}, {:?}] out of bounds for a {}x{} matrix",
+            rows, cols, n_rows, n_cols
+        );
+    }
+}
+
+impl<K: FieldBound> Matrix<K> {
+    pub fn as_view(&self) -> MatrixView<'_, K> {
+        MatrixView {
+            data: self.as_slice(),
+            rows: self.n_rows(),
+            cols: self.n_cols(),
+            row_step: 1,
+            col_step: self.stride(),
+        }
+    }
+
+    pub fn as_view_mut(&mut self) -> MatrixViewMut<'_, K> {
+        let (rows, cols, stride) = (self.n_rows(), self.n_cols(), self.stride());
+        MatrixViewMut {
+            data: self.as_mut_slice(),
+            rows,
+            cols,
+            row_step: 1,
+            col_step: stride,
+        }
+    }
+
+    pub fn view(&self, rows: ops::Range<usize>, cols: ops::Range<usize>) -> MatrixView<'_, K> {
+        self.as_view().view(rows, cols)
+    }
+
+    pub fn view_mut(
+        &mut self,
+        rows: ops::Range<usize>,
+        cols: ops::Range<usize>,
+    ) -> MatrixViewMut<'_, K> {
+        self.as_view_mut().into_view_mut(rows, cols)
+    }
+
+    // Row i as a 1 x n view, strided through the column-major buffer
+    pub fn row_view(&self, i: usize) -> MatrixView<'_, K> {
+        self.view(i..i + 1, 0..self.n_cols())
+    }
+
+    pub fn transpose_view(&self) -> MatrixView<'_, K> {
+        self.as_view().transpose()
+    }
+}
+
+impl<'a, K: FieldBound> MatrixView<'a, K> {
+    pub fn n_rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn n_cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn shape(&self) -> Dimension {
+        Dimension::D2(D2 {
+            rows: self.rows,
+            cols: self.cols,
+        })
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.rows == self.cols
+    }
+
+    pub fn view(&self, rows: ops::Range<usize>, cols: ops::Range<usize>) -> MatrixView<'a, K> {
+        check_ranges(&rows, &cols, (self.rows, self.cols));
+
+        let (n_rows, n_cols) = (rows.len(), cols.len());
+        let (start, len) = block_span(
+            (rows.start, cols.start),
+            (n_rows, n_cols),
+            (self.row_step, self.col_step),
+        );
+
+        MatrixView {
+            data: &self.data[start..start + len],
+            rows: n_rows,
+            cols: n_cols,
+            row_step: self.row_step,
+            col_step: self.col_step,
+        }
+    }
+
+    pub fn transpose(&self) -> MatrixView<'a, K> {
+        MatrixView {
+            data: self.data,
+            rows: self.cols,
+            cols: self.rows,
+            row_step: self.col_step,
+            col_step: self.row_step,
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'a K> + '_ {
+        let data = self.data;
+        (0..self.cols).flat_map(move |j| {
+            (0..self.rows).map(move |i| &data[i * self.row_step + j * self.col_step])
+        })
+    }
+
+    pub fn to_matrix(&self) -> Matrix<K> {
+        Matrix::from_col_major(self.rows, self.cols, self.iter().cloned().collect())
+    }
+
+    pub fn sum(&self) -> K {
+        let mut sum = K::ZERO;
+        for x in self.iter() {
+            sum += x;
+        }
+        sum
+    }
+
+    pub fn norm(&self) -> K {
+        let mut sqsum = K::ZERO;
+        for x in self.iter() {
+            let mut sq = x.clone();
+            sq *= &x.conjugate();
+            sqsum += &sq;
+        }
+        sqsum.sqrt()
+    }
+
+    pub fn norm_1(&self) -> K {
+        let mut sum = K::ZERO;
+        for x in self.iter() {
+            sum += &x.abs();
+        }
+        sum
+    }
+
+    pub fn norm_inf(&self) -> K {
+        let mut max = K::ZERO;
+        for x in self.iter() {
+            if x.abs() > max {
+                max = x.abs();
+            }
+        }
+        max
+    }
+
+    pub fn trace(&self) -> K {
+        match self.try_trace() {
+            Ok(t) => t,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    pub fn try_trace(&self) -> Result<K, MatrixError> {
+        if self.rows == 0 || self.cols == 0 {
+            return Err(MatrixError::Empty { operation: "trace" });
+        }
+        if !self.is_square() {
+            return Err(MatrixError::NotSquare {
+                operation: "trace",
+                shape: self.shape(),
+            });
+        }
+
+        let mut sum = K::ZERO;
+        for i in 0..self.rows {
+            sum += &self[(i, i)];
+        }
+        Ok(sum)
+    }
+
+    pub fn mul_vec(&self, v: &Vector<K>) -> Vector<K> {
+        match self.try_mul_vec(v) {
+            Ok(u) => u,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    pub fn try_mul_vec(&self, v: &Vector<K>) -> Result<Vector<K>, MatrixError> {
+        if v.len() != self.cols {
+            return Err(MatrixError::size("mul_vec", self.cols, v.len()));
+        }
+
+        let mut y = vec![K::ZERO; self.rows];
+        for (j, v_j) in v.iter().enumerate() {
+            for (i, y_i) in y.iter_mut().enumerate() {
+                *y_i += &(self[(i, j)].clone() * v_j.clone());
+            }
+        }
+
+        Ok(Vector::from(y))
+    }
+
+    pub fn mul_mat(&self, m: &MatrixView<K>) -> Matrix<K> {
+        match self.try_mul_mat(m) {
+            Ok(p) => p,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    pub fn try_mul_mat(&self, m: &MatrixView<K>) -> Result<Matrix<K>, MatrixError> {
+        if self.cols != m.rows {
+            return Err(MatrixError::incompatible(
+                "mul_mat",
+                Dimension::D2(D2 {
+                    rows: self.cols,
+                    cols: m.cols,
+                }),
+                m.shape(),
+            ));
+        }
+
+        let mut data = vec![K::ZERO; self.rows * m.cols];
+        for (j, out) in data.chunks_mut(self.rows.max(1)).enumerate() {
+            for k in 0..self.cols {
+                let b_kj = &m[(k, j)];
+                for (i, c_ij) in out.iter_mut().enumerate() {
+                    *c_ij += &(self[(i, k)].clone() * b_kj.clone());
+                }
+            }
+        }
+
+        Ok(Matrix::from_col_major(self.rows, m.cols, data))
+    }
+}
+
+impl<K: FieldBound> ops::Index<(usize, usize)> for MatrixView<'_, K> {
+    type Output = K;
+    fn index(&self, (i, j): (usize, usize)) -> &K {
+        if i >= self.rows || j >= self.cols {
+            panic!(
+                "index ({}, {}) out of bounds for a {}x{} view",
+                i, j, self.rows, self.cols
+            );
+        }
+        &self.data[i * self.row_step + j * self.col_step]
+    }
+}
+
+impl<K: FieldBound> PartialEq for MatrixView<'_, K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.rows == other.rows && self.cols == other.cols && self.iter().eq(other.iter())
+    }
+}
+
+impl<'a, K: FieldBound> MatrixViewMut<'a, K> {
+    pub fn n_rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn n_cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn as_view(&self) -> MatrixView<'_, K> {
+        MatrixView {
+            data: self.data,
+            rows: self.rows,
+            cols: self.cols,
+            row_step: self.row_step,
+            col_step: self.col_step,
+        }
+    }
+
+    pub fn view_mut(
+        &mut self,
+        rows: ops::Range<usize>,
+        cols: ops::Range<usize>,
+    ) -> MatrixViewMut<'_, K> {
+        MatrixViewMut {
+            data: self.data,
+            rows: self.rows,
+            cols: self.cols,
+            row_step: self.row_step,
+            col_step: self.col_step,
+        }
+        .into_view_mut(rows, cols)
+    }
+
+    pub fn into_view_mut(
+        self,
+        rows: ops::Range<usize>,
+        cols: ops::Range<usize>,
+    ) -> MatrixViewMut<'a, K> {
+        check_ranges(&rows, &cols, (self.rows, self.cols));
+
+        let (n_rows, n_cols) = (rows.len(), cols.len());
+        let (start, len) = block_span(
+            (rows.start, cols.start),
+            (n_rows, n_cols),
+            (self.row_step, self.col_step),
+        );
+
+        MatrixViewMut {
+            data: &mut self.data[start..start + len],
+            rows: n_rows,
+            cols: n_cols,
+            row_step: self.row_step,
+            col_step: self.col_step,
+        }
+    }
+
+    pub fn transpose(self) -> MatrixViewMut<'a, K> {
+        MatrixViewMut {
+            data: self.data,
+            rows: self.cols,
+            cols: self.rows,
+            row_step: self.col_step,
+            col_step: self.row_step,
+        }
+    }
+
+    pub fn norm(&self) -> K {
+        self.as_view().norm()
+    }
+
+    pub fn trace(&self) -> K {
+        self.as_view().trace()
+    }
+
+    pub fn mul_vec(&self, v: &Vector<K>) -> Vector<K> {
+        self.as_view().mul_vec(v)
+    }
+
+    pub fn mul_mat(&self, m: &MatrixView<K>) -> Matrix<K> {
+        self.as_view().mul_mat(m)
+    }
+
+    fn for_each_mut(&mut self, mut f: impl FnMut(usize, usize, &mut K)) {
+        for j in 0..self.cols {
+            for i in 0..self.rows {
+                f(i, j, &mut self.data[i * self.row_step + j * self.col_step]);
+            }
+        }
+    }
+
+    pub fn fill(&mut self, k: K) {
+        self.for_each_mut(|_, _, x| *x = k.clone());
+    }
+
+    pub fn scl(&mut self, a: K) {
+        self.for_each_mut(|_, _, x| *x *= &a);
+    }
+
+    fn check_shape(&self, operation: &'static str, v: &MatrixView<K>) -> Result<(), MatrixError> {
+        if self.rows != v.rows || self.cols != v.cols {
+            return Err(MatrixError::incompatible(
+                operation,
+                self.as_view().shape(),
+                v.shape(),
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn add(&mut self, v: &MatrixView<K>) {
+        if let Err(e) = self.try_add(v) {
+            panic!("{}", e);
+        }
+    }
+
+    pub fn try_add(&mut self, v: &MatrixView<K>) -> Result<(), MatrixError> {
+        self.check_shape("add", v)?;
+        self.for_each_mut(|i, j, x| *x += &v[(i, j)]);
+        Ok(())
+    }
+
+    pub fn sub(&mut self, v: &MatrixView<K>) {
+        if let Err(e) = self.try_sub(v) {
+            panic!("{}", e);
+        }
+    }
+
+    pub fn try_sub(&mut self, v: &MatrixView<K>) -> Result<(), MatrixError> {
+        self.check_shape("sub", v)?;
+        self.for_each_mut(|i, j, x| *x -= &v[(i, j)]);
+        Ok(())
+    }
+
+    pub fn copy_from(&mut self, v: &MatrixView<K>) {
+        if let Err(e) = self.try_copy_from(v) {
+            panic!("{}", e);
+        }
+    }
+
+    pub fn try_copy_from(&mut self, v: &MatrixView<K>) -> Result<(), MatrixError> {
+        self.check_shape("copy_from", v)?;
+        self.for_each_mut(|i, j, x| *x = v[(i, j)].clone());
+        Ok(())
+    }
+}
+
+impl<K: FieldBound> ops::Index<(usize, usize)> for MatrixViewMut<'_, K> {
+    type Output = K;
+    fn index(&self, (i, j): (usize, usize)) -> &K {
+        if i >= self.rows || j >= self.cols {
+            panic!(
+                "index ({}, {}) out of bounds for a {}x{} view",
+                i, j, self.rows, self.cols
+            );
+        }
+        &self.data[i * self.row_step + j * self.col_step]
+    }
+}
+
+impl<K: FieldBound> ops::IndexMut<(usize, usize)> for MatrixViewMut<'_, K> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut K {
+        if i >= self.rows || j >= self.cols {
+            panic!(
+                "index ({}, {}) out of bounds for a {}x{} view",
+                i, j, self.rows, self.cols
+            );
+        }
+        &mut self.data[i * self.row_step + j * self.col_step]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::fixtures::sample;
+    use crate::matrix::matrix;
+    use crate::vector::vector;
+    use crate::VectorSpace;
+
+    #[test]
+    fn view_test() {
+        let m = sample();
+        let v = m.view(1..3, 1..4);
+
+        assert_eq!(v.shape(), Dimension::D2(D2 { rows: 2, cols: 3 }));
+        assert_eq!(v[(0, 0)], 6);
+        assert_eq!(v[(1, 2)], 12);
+        assert_eq!(v.to_matrix(), matrix![[6, 7, 8], [10, 11, 12]]);
+
+        // Views of views stay relative to the parent view
+        assert_eq!(v.view(1..2, 0..2).to_matrix(), matrix![[10, 11]]);
+        assert_eq!(m.view(0..0, 0..4).n_rows(), 0);
+    }
+
+    #[test]
+    fn strided_view_test() {
+        let m = sample();
+
+        assert_eq!(m.row_view(2).to_matrix(), matrix![[9, 10, 11, 12]]);
+        assert_eq!(m.transpose_view().to_matrix(), m.transpose());
+        assert_eq!(
+            m.view(0..2, 1..4).transpose().to_matrix(),
+            matrix![[2, 6], [3, 7], [4, 8]]
+        );
+        assert_eq!(
+            m.transpose_view().view(1..2, 0..4),
+            m.view(0..4, 1..2).transpose()
+        );
+    }
+
+    #[test]
+    fn read_ops_test() {
+        let m = sample();
+        let v = m.view(1..3, 1..3);
+
+        assert_eq!(v.trace(), 17);
+        assert_eq!(v.sum(), 34);
+        assert_eq!(v.norm_1(), 34);
+        assert_eq!(v.norm_inf(), 11);
+        assert_eq!(v.norm(), v.to_matrix().norm());
+        assert_eq!(v.mul_vec(&vector![1, -1]), vector![-1, -1]);
+        assert_eq!(
+            v.mul_mat(&m.view(0..2, 0..1)),
+            v.to_matrix().mul_mat(&matrix![[1], [5]])
+        );
+        assert_eq!(
+            v.transpose().mul_mat(&v),
+            v.to_matrix().transpose().mul_mat(&v.to_matrix())
+        );
+        assert!(m.view(0..2, 0..3).try_trace().is_err());
+        assert!(v.try_mul_vec(&vector![1, 2, 3]).is_err());
+        assert!(v.try_mul_mat(&m.view(0..3, 0..1)).is_err());
+    }
+
+    #[test]
+    fn non_finite_mul_mat_test() {
+        // 0 * inf is NaN, the same as in the dense product
+        let a = matrix![[f64::INFINITY, 1.], [1., 1.]];
+        let b = matrix![[0., 1.], [0., 1.]];
+        let dense = a.mul_mat(&b);
+        let view = a.view(0..2, 0..2).mul_mat(&b.view(0..2, 0..2));
+
+        assert!(dense[(0, 0)].is_nan() && view[(0, 0)].is_nan());
+        assert_eq!(view[(0, 1)], dense[(0, 1)]);
+        assert_eq!(view.view(1..2, 0..2), dense.view(1..2, 0..2));
+    }
+
+    #[test]
+    fn view_mut_test() {
+        let mut m = sample();
+
+        let mut block = m.view_mut(2..4, 2..4);
+        block.scl(10);
+        block[(0, 1)] = 0;
+        assert_eq!(block.trace(), 270);
+
+        assert_eq!(
+            m,
+            matrix![
+                [1, 2, 3, 4],
+                [5, 6, 7, 8],
+                [9, 10, 110, 0],
+                [13, 14, 150, 160]
+            ]
+        );
+
+        let src = sample();
+        let mut top = m.view_mut(0..2, 0..4);
+        top.sub(&src.view(0..2, 0..4));
+        top.view_mut(0..1, 0..2).fill(7);
+        assert!(top.try_add(&src.view(0..1, 0..4)).is_err());
+
+        assert_eq!(m.row_view(0).to_matrix(), matrix![[7, 7, 0, 0]]);
+        assert_eq!(m.row_view(1).to_matrix(), matrix![[0, 0, 0, 0]]);
+    }
+
+    #[test]
+    fn transposed_view_mut_test() {
+        let mut m = sample();
+        let src = sample();
+
+        // Writes the transpose of the top-left block into it
+        m.view_mut(0..2, 0..2)
+            .transpose()
+            .copy_from(&src.view(0..2, 0..2));
+
+        assert_eq!(m.view(0..2, 0..2).to_matrix(), matrix![[1, 5], [2, 6]]);
+        assert_eq!(m[(2, 2)], 11);
+    }
+
+    #[test]
+    fn empty_view_test() {
+        let mut m = sample();
+
+        // Empty blocks starting on the far edge are still in bounds
+        assert_eq!(
+            m.view(4..4, 4..4).shape(),
+            Dimension::D2(D2 { rows: 0, cols: 0 })
+        );
+        assert_eq!(m.view(0..2, 0..3).view(2..2, 3..3).n_cols(), 0);
+        assert_eq!(m.view(1..4, 4..4).to_matrix().size(), 0);
+        assert_eq!(m.view_mut(4..4, 0..4).n_rows(), 0);
+        assert_eq!(m.view_mut(0..2, 1..4).into_view_mut(2..2, 3..3).n_rows(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_bounds_test() {
+        sample().view(2..5, 0..1);
+    }
+}